@@ -2,7 +2,11 @@ use std::env;
 
 fn main() {
     println!("cargo::rerun-if-changed=.env");
+    println!("cargo::rerun-if-env-changed=DISCORD_CLIENT_ID");
+    // Only a fallback, the game also reads DISCORD_CLIENT_ID and the settings file at runtime.
     if dotenvy::dotenv().is_ok(){
-        println!("cargo::rustc-env=DISCORD_CLIENT_ID={}",env::var("DISCORD_CLIENT_ID").unwrap());
+        if let Ok(client_id) = env::var("DISCORD_CLIENT_ID") {
+            println!("cargo::rustc-env=DISCORD_CLIENT_ID={}",client_id);
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,269 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy_persistent::{Persistent, StorageFormat};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    events::{RunFinished, UpgradeAcquired},
+    generate::{self, Generator},
+    input::Player,
+    level_upgrades::UpgradeScreen,
+    stats::RunStats,
+    upgrades::{Upgrade, UpgradeLevel},
+    AppState, InGameState,
+};
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        let config_dir = crate::benchmark::profile_dir();
+        app.insert_resource(
+            AchievementsResource::builder()
+                .name("achievements")
+                .format(StorageFormat::Toml)
+                .path(config_dir.join("achievements.toml"))
+                .default(AchievementProfile::default())
+                .build()
+                .expect("Failed to load achievements"),
+        );
+        app.add_event::<AchievementUnlocked>();
+        app.init_resource::<GlideStreak>();
+        app.add_systems(OnEnter(AppState::InGame), reset_glide_streak);
+        app.add_systems(
+            Update,
+            (
+                distance_achievement,
+                mythic_upgrade_achievement,
+                glide_streak_achievement,
+                unlock_achievements,
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        // The run's stats are final once the game reaches the end screen.
+        app.add_systems(
+            Update,
+            (finish_run_achievements, unlock_achievements)
+                .chain()
+                .run_if(in_state(InGameState::End)),
+        );
+    }
+}
+
+#[derive(EnumIter, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Achievement {
+    LongHaul,
+    Mythic,
+    Grounded,
+    Glider,
+}
+impl Achievement {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::LongHaul => "Long Haul",
+            Achievement::Mythic => "Mythic",
+            Achievement::Grounded => "Grounded",
+            Achievement::Glider => "Glider",
+        }
+    }
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::LongHaul => "Reach 10,000 distance",
+            Achievement::Mythic => "Collect a Mythic upgrade",
+            Achievement::Grounded => "Finish a timed run without dashing",
+            Achievement::Glider => "Glide over 5 consecutive holes",
+        }
+    }
+}
+
+const LONG_HAUL_DISTANCE: f32 = 10_000.;
+const GLIDER_HOLES: u32 = 5;
+
+pub type AchievementsResource = Persistent<AchievementProfile>;
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct AchievementProfile {
+    #[serde(default)]
+    pub unlocked: Vec<UnlockedAchievement>,
+}
+impl AchievementProfile {
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked
+            .iter()
+            .any(|unlocked| unlocked.achievement == achievement)
+    }
+    /// Every achievement with whether it has been unlocked.
+    pub fn all(&self) -> impl Iterator<Item = (Achievement, bool)> + '_ {
+        Achievement::iter().map(|achievement| (achievement, self.is_unlocked(achievement)))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnlockedAchievement {
+    pub achievement: Achievement,
+    /// Seconds since the unix epoch.
+    pub unlocked: u64,
+}
+
+#[derive(Event)]
+struct AchievementUnlocked(Achievement);
+
+/// Holes passed in a row in the current glide.
+#[derive(Resource, Default)]
+struct GlideStreak {
+    holes: u32,
+    column: usize,
+}
+
+fn reset_glide_streak(mut streak: ResMut<GlideStreak>) {
+    *streak = GlideStreak::default();
+}
+
+fn distance_achievement(
+    player: Query<&Player>,
+    profile: Res<AchievementsResource>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    if profile.is_unlocked(Achievement::LongHaul) {
+        return;
+    }
+    if let Ok(player) = player.get_single() {
+        if player.distance >= LONG_HAUL_DISTANCE {
+            unlocked.send(AchievementUnlocked(Achievement::LongHaul));
+        }
+    }
+}
+
+fn mythic_upgrade_achievement(
+    mut upgrades: EventReader<UpgradeAcquired>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    for UpgradeAcquired(upgrade) in upgrades.read() {
+        if upgrade.tier() == UpgradeLevel::Mythic {
+            unlocked.send(AchievementUnlocked(Achievement::Mythic));
+        }
+    }
+}
+
+fn glide_streak_achievement(
+    player: Query<(&Transform, &Player)>,
+    mut streak: ResMut<GlideStreak>,
+    mut generator: ResMut<Generator>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    let Ok((transform, player)) = player.get_single() else {
+        return;
+    };
+    if player.glide_timer.is_none() {
+        streak.holes = 0;
+        return;
+    }
+    let column = generate::column(transform.translation.x);
+    if column == streak.column {
+        return;
+    }
+    streak.column = column;
+    if generator.is_gap(column) {
+        streak.holes += 1;
+        if streak.holes >= GLIDER_HOLES {
+            unlocked.send(AchievementUnlocked(Achievement::Glider));
+        }
+    } else {
+        streak.holes = 0;
+    }
+}
+
+fn finish_run_achievements(
+    stats: Res<RunStats>,
+    mut finished: EventReader<RunFinished>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    for finished in finished.read() {
+        if finished.timed_out && stats.dashes == 0 {
+            unlocked.send(AchievementUnlocked(Achievement::Grounded));
+        }
+    }
+}
+
+fn unlock_achievements(
+    mut commands: Commands,
+    mut unlocked: EventReader<AchievementUnlocked>,
+    mut profile: ResMut<AchievementsResource>,
+    safe_ui: Query<Entity, With<crate::SafeUi>>,
+) {
+    for AchievementUnlocked(achievement) in unlocked.read() {
+        if profile.is_unlocked(*achievement) {
+            continue;
+        }
+        info!("Achievement unlocked: {:?}", achievement);
+        let record = UnlockedAchievement {
+            achievement: *achievement,
+            unlocked: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        };
+        if let Err(err) = profile.update(|profile| profile.unlocked.push(record.clone())) {
+            error!("Failed to save achievements: {}", err);
+        }
+        if let Ok(safe_ui) = safe_ui.get_single() {
+            spawn_achievement_toast(&mut commands, safe_ui, *achievement);
+        }
+    }
+}
+
+/// Slides in from the bottom left like an upgrade notification.
+fn spawn_achievement_toast(commands: &mut Commands, safe_ui: Entity, achievement: Achievement) {
+    commands.entity(safe_ui).with_children(|ui| {
+        ui.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(25.),
+                    height: Val::Auto,
+                    left: Val::Percent(2.),
+                    bottom: Val::Px(-100.),
+                    ..default()
+                },
+                ..default()
+            },
+            UpgradeScreen::new(),
+        ))
+        .with_children(|screen| {
+            screen
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        align_items: AlignItems::Center,
+                        justify_items: JustifyItems::Center,
+                        max_width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        border: UiRect::all(Val::Px(5.)),
+                        padding: UiRect::all(Val::Px(5.)),
+                        ..default()
+                    },
+                    border_color: Color::GOLD.into(),
+                    background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                    ..default()
+                })
+                .with_children(|container| {
+                    container.spawn(
+                        TextBundle::from_section(
+                            format!(
+                                "Achievement Unlocked\n{}\n{}",
+                                achievement.title(),
+                                achievement.description()
+                            ),
+                            TextStyle {
+                                font_size: 20.,
+                                ..default()
+                            },
+                        )
+                        .with_text_justify(JustifyText::Center),
+                    );
+                });
+        });
+    });
+}
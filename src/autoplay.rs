@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_tnua::controller::TnuaController;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    generate::Generator,
+    input::{Action, Player},
+    AppState, InGameState,
+};
+
+pub mod plan;
+
+/// Lets a scripted bot play instead of the keyboard, enabled with `--autoplay`.
+pub struct AutoplayPlugin;
+
+impl Plugin for AutoplayPlugin {
+    fn build(&self, app: &mut App) {
+        if std::env::args().any(|arg| arg == "--autoplay") {
+            app.init_resource::<Autoplay>();
+        }
+        app.add_systems(
+            PreUpdate,
+            (take_control, autoplay)
+                .chain()
+                .in_set(InputManagerSystem::ManualControl)
+                .run_if(
+                    resource_exists::<Autoplay>
+                        .and_then(in_state(AppState::InGame))
+                        .and_then(in_state(InGameState::Playing)),
+                ),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Autoplay;
+
+/// Without an input map the input manager leaves the action state to the bot.
+pub fn take_control(mut commands: Commands, player: Query<Entity, With<InputMap<Action>>>) {
+    for player in player.iter() {
+        commands.entity(player).remove::<InputMap<Action>>();
+    }
+}
+
+fn autoplay(
+    mut player: Query<(
+        &Transform,
+        &Velocity,
+        &TnuaController,
+        &mut Player,
+        &mut ActionState<Action>,
+    )>,
+    mut generator: ResMut<Generator>,
+) {
+    let Ok((transform, velocity, controller, mut player, mut action_state)) =
+        player.get_single_mut()
+    else {
+        return;
+    };
+    let state = plan::BotState {
+        x: transform.translation.x,
+        y: transform.translation.y,
+        velocity_y: velocity.linvel.y,
+        grounded: matches!(controller.is_airborne(), Ok(false)),
+        speed: player.max_speed(),
+        air_jumps: if player.jump_skill.air {
+            player
+                .jump_skill
+                .max_jumps
+                .saturating_sub(1 + player.jump_index as u8)
+        } else {
+            0
+        },
+        dashes: player
+            .dash_skill
+            .max_dash
+            .saturating_sub(player.used_dashes),
+        glides: player
+            .glide_skill
+            .max_uses
+            .saturating_sub(player.used_glides),
+    };
+    let decision = plan::decide(&mut generator, &state);
+    action_state.press(&Action::Right);
+    if decision.air_jump {
+        action_state.release(&Action::Jump);
+    }
+    for (action, pressed) in [
+        (Action::Jump, decision.jump),
+        (Action::Dash, decision.dash),
+        (Action::Glide, decision.glide),
+    ] {
+        if pressed {
+            action_state.press(&action);
+        } else {
+            action_state.release(&action);
+        }
+    }
+}
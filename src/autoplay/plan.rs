@@ -0,0 +1,84 @@
+//! Lookahead over the generator, shared by the in-game bot and the `autoplay_sim` tool.
+use crate::{
+    generate::{column, Generator, COLUMN_WIDTH, MAX_HOLE_STREAK},
+    player::FLOAT_HEIGHT,
+};
+
+/// How far ahead the bot looks, in seconds of running.
+const LOOKAHEAD_SECS: f32 = 0.35;
+/// Rises up to this are walked over without jumping.
+pub const STEP_HEIGHT: f32 = 0.6;
+
+/// Top of the floor cube in column `x`, `None` over a gap.
+pub fn surface(generator: &mut Generator, x: usize) -> Option<f32> {
+    if generator.is_gap(x) {
+        None
+    } else {
+        Some(generator.get_height(x) as f32 + 1.)
+    }
+}
+
+/// What the bot knows about the player each tick.
+#[derive(Debug, Clone, Copy)]
+pub struct BotState {
+    pub x: f32,
+    pub y: f32,
+    pub velocity_y: f32,
+    pub grounded: bool,
+    pub speed: f32,
+    pub air_jumps: u8,
+    pub dashes: u8,
+    pub glides: u8,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Decision {
+    /// Hold jump, for a full height jump off the ground.
+    pub jump: bool,
+    /// Press jump again while airborne.
+    pub air_jump: bool,
+    pub dash: bool,
+    pub glide: bool,
+}
+
+/// Runs right, jumping before gaps and rises, and spends air jumps, dashes and glides when a
+/// fall would otherwise end short of solid ground.
+pub fn decide(generator: &mut Generator, state: &BotState) -> Decision {
+    let here = column(state.x);
+    let ahead = ((state.speed * LOOKAHEAD_SECS) / COLUMN_WIDTH)
+        .ceil()
+        .max(1.) as usize;
+    let feet = state.y - FLOAT_HEIGHT;
+    let mut gap_ahead = false;
+    let mut highest = f32::MIN;
+    for x in here + 1..=here + ahead {
+        match surface(generator, x) {
+            Some(top) => highest = highest.max(top),
+            None => gap_ahead = true,
+        }
+    }
+    let mut decision = Decision::default();
+    if state.grounded {
+        decision.jump = gap_ahead || highest - feet > STEP_HEIGHT;
+        return decision;
+    }
+    decision.jump = state.velocity_y > 0.;
+    if state.velocity_y > 0. {
+        return decision;
+    }
+    let over_gap = surface(generator, here).is_none();
+    let below_ledge = highest - feet > 0.;
+    if (over_gap || below_ledge) && state.air_jumps > 0 {
+        decision.air_jump = true;
+        decision.jump = true;
+    } else if over_gap {
+        let landing = (here + 1..here + 2 * MAX_HOLE_STREAK)
+            .find(|x| surface(generator, *x).is_some())
+            .unwrap_or(here);
+        let distance = (landing - here) as f32 * COLUMN_WIDTH;
+        // A dash covers three quarters of a second of running.
+        decision.dash = state.dashes > 0 && distance <= state.speed * 0.75;
+        decision.glide = !decision.dash && state.glides > 0;
+    }
+    decision
+}
@@ -0,0 +1,262 @@
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{
+    app::AppExit,
+    diagnostic::{DiagnosticsPlugin, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    ecs::entity::Entities,
+    prelude::*,
+    window::PresentMode,
+};
+use bevy_framepace::{FramepaceSettings, Limiter};
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    autoplay::take_control,
+    diagnostics_recorder::DiagnosticsRecorderPlugin,
+    game_mode::GameMode,
+    generate::{Generator, NoiseSettings},
+    headless_app,
+    input::{Action, Player},
+    level_diagnostics::LevelDiagnosticsPlugin,
+    system_info::SystemInformationDiagnosticsPlugin,
+    AppState, InGameState,
+};
+
+/// Plays a fixed seed with a scripted autopilot for a fixed time and prints frame time and
+/// entity statistics on exit, enabled with `--benchmark`. Add `--headless` to run without a
+/// window or renderer.
+pub struct BenchmarkPlugin;
+
+impl Plugin for BenchmarkPlugin {
+    fn build(&self, app: &mut App) {
+        if !benchmark_requested() {
+            return;
+        }
+        app.insert_resource(Generator::from_u64_seed(
+            BENCHMARK_SEED,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        ));
+        // Falls respawn on a checkpoint, so a bad jump doesn't cut the benchmark short.
+        app.insert_resource(GameMode::Practice);
+        app.init_resource::<Benchmark>();
+        app.add_systems(Startup, start_benchmark);
+        app.add_systems(
+            PreUpdate,
+            (take_control, autopilot)
+                .chain()
+                .in_set(InputManagerSystem::ManualControl)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(
+            Update,
+            (uncap_frame_rate, measure_frame)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(OnEnter(InGameState::End), run_ended);
+        app.add_systems(Last, report_on_exit);
+    }
+}
+
+const BENCHMARK_SEED: u64 = 0;
+const BENCHMARK_DURATION: Duration = Duration::from_secs(60);
+/// The autopilot holds jump for `JUMP_HOLD` at the start of every `JUMP_INTERVAL`.
+const JUMP_INTERVAL: Duration = Duration::from_millis(1200);
+const JUMP_HOLD: Duration = Duration::from_millis(400);
+
+pub fn benchmark_requested() -> bool {
+    std::env::args().any(|arg| arg == "--benchmark")
+}
+
+/// Where scores, stats, daily runs and achievements are saved. A benchmark gets a throwaway
+/// directory, so the autopilot's runs never end up in the player's profile.
+pub fn profile_dir() -> PathBuf {
+    if benchmark_requested() {
+        std::env::temp_dir().join(concat!(env!("CARGO_PKG_NAME"), "-benchmark"))
+    } else {
+        dirs::config_dir().unwrap().join(env!("CARGO_PKG_NAME"))
+    }
+}
+
+pub fn headless_requested() -> bool {
+    benchmark_requested() && std::env::args().any(|arg| arg == "--headless")
+}
+
+/// Runs the benchmark with the gameplay plugins and no window, renderer or audio, so
+/// generation and physics can be measured on their own.
+pub fn run_headless() {
+    let mut app = headless_app();
+    app.add_plugins((
+        DiagnosticsPlugin,
+        FrameTimeDiagnosticsPlugin,
+        EntityCountDiagnosticsPlugin,
+        SystemInformationDiagnosticsPlugin,
+        LevelDiagnosticsPlugin,
+        DiagnosticsRecorderPlugin,
+    ));
+    app.insert_state(AppState::MainMenu);
+    app.insert_state(InGameState::Playing);
+    app.add_plugins(BenchmarkPlugin);
+    app.run();
+}
+
+#[derive(Resource)]
+struct Benchmark {
+    duration: Duration,
+    /// Real time spent playing.
+    elapsed: Duration,
+    /// Milliseconds per frame.
+    frame_times: Vec<f64>,
+    peak_entities: u32,
+    ended_early: bool,
+    reported: bool,
+}
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            duration: BENCHMARK_DURATION,
+            elapsed: Duration::ZERO,
+            frame_times: Vec::new(),
+            peak_entities: 0,
+            ended_early: false,
+            reported: false,
+        }
+    }
+}
+
+fn start_benchmark(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::InGame);
+}
+
+/// Frame times capped by vsync or the frame limiter don't say anything about performance. Kept
+/// up every frame since the settings apply the frame limit whenever they change.
+fn uncap_frame_rate(
+    frame_pace: Option<ResMut<FramepaceSettings>>,
+    mut windows: Query<&mut Window>,
+) {
+    if let Some(mut frame_pace) = frame_pace {
+        if !matches!(frame_pace.limiter, Limiter::Off) {
+            frame_pace.limiter = Limiter::Off;
+        }
+    }
+    for mut window in windows.iter_mut() {
+        if window.present_mode != PresentMode::AutoNoVsync {
+            window.present_mode = PresentMode::AutoNoVsync;
+        }
+    }
+}
+
+/// Holds right and jumps on a schedule of game time, so every benchmark run plays the same way
+/// however fast the frames are.
+fn autopilot(
+    fixed_time: Res<Time<Fixed>>,
+    mut player: Query<&mut ActionState<Action>, With<Player>>,
+) {
+    let Ok(mut action_state) = player.get_single_mut() else {
+        return;
+    };
+    action_state.press(&Action::Right);
+    let into_interval = fixed_time.elapsed().as_nanos() % JUMP_INTERVAL.as_nanos();
+    if into_interval < JUMP_HOLD.as_nanos() {
+        action_state.press(&Action::Jump);
+    } else {
+        action_state.release(&Action::Jump);
+    }
+}
+
+fn measure_frame(
+    mut benchmark: ResMut<Benchmark>,
+    time: Res<Time<Real>>,
+    entities: &Entities,
+    mut exit: EventWriter<AppExit>,
+) {
+    benchmark.elapsed += time.delta();
+    benchmark.frame_times.push(time.delta_seconds_f64() * 1000.);
+    benchmark.peak_entities = benchmark.peak_entities.max(entities.len());
+    if benchmark.elapsed >= benchmark.duration {
+        exit.send(AppExit);
+    }
+}
+
+fn run_ended(mut benchmark: ResMut<Benchmark>, mut exit: EventWriter<AppExit>) {
+    benchmark.ended_early = true;
+    exit.send(AppExit);
+}
+
+fn report_on_exit(
+    mut benchmark: ResMut<Benchmark>,
+    mut exits: EventReader<AppExit>,
+    generator: Res<Generator>,
+) {
+    if exits.read().count() == 0 || benchmark.reported {
+        return;
+    }
+    benchmark.reported = true;
+    println!("Benchmark");
+    println!(
+        "  seed {} (generator version {})",
+        BENCHMARK_SEED,
+        generator.version()
+    );
+    println!(
+        "  played {:.1}s of {:.0}s{}",
+        benchmark.elapsed.as_secs_f32(),
+        benchmark.duration.as_secs_f32(),
+        if benchmark.ended_early {
+            ", the run ended early"
+        } else {
+            ""
+        }
+    );
+    match FrameTimes::from_samples(&benchmark.frame_times) {
+        Some(frame_times) => println!(
+            "  {} frames, frame time min {:.2}ms avg {:.2}ms p99 {:.2}ms",
+            benchmark.frame_times.len(),
+            frame_times.min,
+            frame_times.average,
+            frame_times.p99
+        ),
+        None => println!("  no frames measured"),
+    }
+    println!("  peak entities {}", benchmark.peak_entities);
+}
+
+#[derive(Debug, PartialEq)]
+struct FrameTimes {
+    min: f64,
+    average: f64,
+    p99: f64,
+}
+impl FrameTimes {
+    fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let p99_index = ((sorted.len() as f64 * 0.99).ceil() as usize).max(1) - 1;
+        Some(Self {
+            min: sorted[0],
+            average: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p99: sorted[p99_index],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn frame_time_percentiles() {
+        let samples: Vec<f64> = (1..=200).rev().map(f64::from).collect();
+        assert_eq!(
+            FrameTimes::from_samples(&samples),
+            Some(FrameTimes {
+                min: 1.,
+                average: 100.5,
+                p99: 198.,
+            })
+        );
+        assert_eq!(FrameTimes::from_samples(&[]), None);
+    }
+}
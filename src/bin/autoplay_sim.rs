@@ -0,0 +1,197 @@
+//! Plays many seeds with the autoplay bot in the headless game, with the same plugins, physics
+//! and character controller as a real run, and reports how far it gets, what ends its runs and
+//! where it gets stuck.
+//!
+//! ```text
+//! cargo run --release --bin autoplay_sim -- [--seeds N] [--seconds S] [--first-seed N]
+//! ```
+//!
+//! Runs are played in Endless mode, so only a death or getting stuck ends them early.
+use std::{collections::HashMap, process::exit};
+
+use bevy::{ecs::event::ManualEventReader, prelude::*, time::TimeUpdateStrategy};
+use sky_scapade::{
+    autoplay::{Autoplay, AutoplayPlugin},
+    events::{DeathCause, PlayerDied},
+    game_mode::GameMode,
+    generate::{column, Generator, NoiseSettings, COLUMN_WIDTH},
+    headless_app,
+    input::Player,
+    AppState, InGameState,
+};
+
+/// Seconds without making progress before the spot counts as unreachable.
+const STALL_LIMIT: f32 = 2.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Outcome {
+    /// Still running when the time was up.
+    Survived,
+    Hole,
+    /// Spikes are the only hazard that kills. Lasers knock the player back and crumbling floors
+    /// drop it, so runs they cost end up counted as holes or walls.
+    Spikes,
+    /// Stuck in front of a rise it couldn't climb.
+    Wall,
+}
+
+struct Run {
+    outcome: Outcome,
+    distance: f32,
+    column: usize,
+}
+
+fn play(seed: u64, seconds: f32) -> Run {
+    // Every update steps the game's fixed timestep once.
+    let tick = Time::<Fixed>::default().timestep();
+    let mut app = headless_app();
+    app.insert_resource(Generator::from_u64_seed(
+        seed,
+        NoiseSettings::new(256_usize, 64, 5),
+        NoiseSettings::new(9_usize, 64, 3),
+    ));
+    app.insert_resource(GameMode::Endless);
+    app.insert_resource(Autoplay);
+    app.add_plugins(AutoplayPlugin);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+    app.insert_state(AppState::InGame);
+    app.insert_state(InGameState::Playing);
+
+    let mut player = app.world.query_filtered::<&Transform, With<Player>>();
+    let mut deaths = ManualEventReader::<PlayerDied>::default();
+    let mut furthest = 0.;
+    let mut stalled = 0.;
+    let end = |outcome, x: f32| Run {
+        outcome,
+        distance: x,
+        column: column(x),
+    };
+    for _ in 0..(seconds / tick.as_secs_f32()).ceil() as usize {
+        app.update();
+        let died = deaths
+            .read(app.world.resource::<Events<PlayerDied>>())
+            .next()
+            .copied();
+        if let Some(died) = died {
+            let outcome = match died.cause {
+                DeathCause::Fall => Outcome::Hole,
+                DeathCause::Spikes => Outcome::Spikes,
+                DeathCause::ChaseWall => unreachable!("Endless runs have no chase wall"),
+            };
+            return end(outcome, died.position.x);
+        }
+        let Ok(transform) = player.get_single(&app.world) else {
+            continue;
+        };
+        let x = transform.translation.x;
+        if x > furthest + COLUMN_WIDTH / 4. {
+            furthest = x;
+            stalled = 0.;
+        } else {
+            stalled += tick.as_secs_f32();
+            if stalled >= STALL_LIMIT {
+                return end(Outcome::Wall, x);
+            }
+        }
+    }
+    let x = player
+        .get_single(&app.world)
+        .map_or(furthest, |transform| transform.translation.x);
+    end(Outcome::Survived, x)
+}
+
+struct Options {
+    seeds: u64,
+    first_seed: u64,
+    seconds: f32,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: autoplay_sim [--seeds N] [--seconds S] [--first-seed N]");
+    exit(2)
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        seeds: 100,
+        first_seed: 0,
+        seconds: 120.,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else { usage() };
+        match flag.as_str() {
+            "--seeds" => options.seeds = value.parse().unwrap_or_else(|_| usage()),
+            "--first-seed" => options.first_seed = value.parse().unwrap_or_else(|_| usage()),
+            "--seconds" => options.seconds = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+    if options.seeds == 0 {
+        usage()
+    }
+    options
+}
+
+fn main() {
+    let options = parse_options();
+    let mut runs = vec![];
+    for seed in options.first_seed..options.first_seed + options.seeds {
+        runs.push((seed, play(seed, options.seconds)));
+    }
+
+    let mut distances: Vec<f32> = runs.iter().map(|(_, run)| run.distance).collect();
+    distances.sort_unstable_by(f32::total_cmp);
+    let quantile = |q: f64| distances[((distances.len() - 1) as f64 * q) as usize];
+    println!(
+        "{} seeds, {}s runs\nDistance: mean {:.0}, min {:.0}, p10 {:.0}, median {:.0}, p90 {:.0}, max {:.0}",
+        options.seeds,
+        options.seconds,
+        distances.iter().sum::<f32>() / distances.len() as f32,
+        distances[0],
+        quantile(0.1),
+        quantile(0.5),
+        quantile(0.9),
+        distances[distances.len() - 1]
+    );
+
+    let mut outcomes: HashMap<Outcome, usize> = HashMap::new();
+    for (_, run) in &runs {
+        *outcomes.entry(run.outcome).or_default() += 1;
+    }
+    println!("\nOutcomes");
+    for outcome in [
+        Outcome::Survived,
+        Outcome::Hole,
+        Outcome::Spikes,
+        Outcome::Wall,
+    ] {
+        let count = outcomes.get(&outcome).copied().unwrap_or_default();
+        let name = format!("{:?}", outcome);
+        println!(
+            "{:>10}{:>8}{:>8.1}%",
+            name,
+            count,
+            count as f64 * 100. / runs.len() as f64
+        );
+    }
+
+    let unreachable: Vec<_> = runs
+        .iter()
+        .filter(|(_, run)| run.outcome == Outcome::Wall)
+        .collect();
+    if !unreachable.is_empty() {
+        println!("\nUnreachable spots (seed, column, world x)");
+        for (seed, run) in unreachable.iter().take(50) {
+            println!(
+                "{:>10}{:>8}{:>10.0}",
+                seed,
+                run.column,
+                run.column as f32 * COLUMN_WIDTH
+            );
+        }
+        if unreachable.len() > 50 {
+            println!("... and {} more", unreachable.len() - 50);
+        }
+    }
+}
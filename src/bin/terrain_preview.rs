@@ -0,0 +1,238 @@
+//! Renders the start of a level to an SVG side profile without opening a window.
+//!
+//! ```text
+//! cargo run --bin terrain_preview -- <seed> [--columns N] [--out FILE]
+//!     [--height-noise WAVE_LENGTH,AMPLITUDE,OCTAVES] [--hole-noise WAVE_LENGTH,AMPLITUDE,OCTAVES]
+//!     [--generator-version V]
+//! ```
+//!
+//! Solid columns are grey, holes red, columns forced back in by the hole streak limit orange and
+//! the estimated position of each timed upgrade is a blue line.
+use std::{fmt::Write, process::exit};
+
+use sky_scapade::{
+    generate::{
+        Generator, NoiseSettings, CHUNK_COLUMNS, COLUMN_WIDTH, GENERATOR_VERSION, SPAWN_COLUMNS,
+    },
+    input::BASE_SPEED,
+    upgrades::{Upgrade, UpgradeLevel, UpgradeType},
+};
+
+/// Mirrors the repeating `Level::upgrade_timer`.
+const UPGRADE_INTERVAL_SECS: f32 = 10.;
+const PIXELS_PER_COLUMN: f64 = 2.;
+const PIXELS_PER_UNIT: f64 = 2.;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Column {
+    Solid,
+    Hole,
+    /// A hole the spawner fills in because the streak got too long.
+    Forced,
+}
+
+struct Options {
+    seed: [u8; 32],
+    columns: usize,
+    out: String,
+    height_noise: NoiseSettings,
+    hole_noise: NoiseSettings,
+    version: u8,
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: terrain_preview <seed> [--columns N] [--out FILE] \
+         [--height-noise W,A,O] [--hole-noise W,A,O] [--generator-version V]"
+    );
+    exit(2)
+}
+
+fn parse_noise(value: &str) -> NoiseSettings {
+    let parts: Vec<&str> = value.split(',').collect();
+    let [wave_length, amplitude, octaves] = parts[..] else {
+        usage()
+    };
+    match (
+        wave_length.parse::<usize>(),
+        amplitude.parse::<f64>(),
+        octaves.parse::<usize>(),
+    ) {
+        (Ok(wave_length), Ok(amplitude), Ok(octaves)) => {
+            let settings = NoiseSettings::new(wave_length, amplitude, octaves);
+            if !settings.is_valid() {
+                usage()
+            }
+            settings
+        }
+        _ => usage(),
+    }
+}
+
+fn parse_options() -> Options {
+    let mut args = std::env::args().skip(1);
+    let Some(seed_text) = args.next() else {
+        usage()
+    };
+    let mut seed = [0_u8; 32];
+    for (byte, input) in seed.iter_mut().zip(seed_text.bytes()) {
+        *byte = input;
+    }
+    // Same defaults as the New Game menu.
+    let mut options = Options {
+        seed,
+        columns: 4096,
+        out: format!("{}.svg", seed_text),
+        height_noise: NoiseSettings::new(256_usize, 64, 5),
+        hole_noise: NoiseSettings::new(9_usize, 64, 3),
+        version: GENERATOR_VERSION,
+    };
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else { usage() };
+        match flag.as_str() {
+            "--columns" => options.columns = value.parse().unwrap_or_else(|_| usage()),
+            "--out" => options.out = value,
+            "--height-noise" => options.height_noise = parse_noise(&value),
+            "--hole-noise" => options.hole_noise = parse_noise(&value),
+            "--generator-version" => options.version = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+    if options.columns == 0 {
+        usage()
+    }
+    options
+}
+
+/// Uses the spawner's own gap rule, so holes it fills back in show up as forced.
+fn columns(generator: &mut Generator, count: usize) -> Vec<(f64, Column)> {
+    let mut result = Vec::with_capacity(count);
+    for start in (0..count).step_by(CHUNK_COLUMNS) {
+        for (offset, height) in generator.get_heights(start).into_iter().enumerate() {
+            let x = start + offset;
+            if x >= count {
+                break;
+            }
+            let column = if generator.is_gap(x) {
+                Column::Hole
+            } else if x >= SPAWN_COLUMNS && generator.is_hole(x) {
+                Column::Forced
+            } else {
+                Column::Solid
+            };
+            result.push((height, column));
+        }
+    }
+    result
+}
+
+/// Where each timed upgrade lands if the player keeps running at full speed.
+fn upgrade_marks(generator: &mut Generator, count: usize) -> Vec<(usize, UpgradeLevel)> {
+    let mut result = vec![];
+    let mut speed = BASE_SPEED;
+    let mut distance = 0.;
+    loop {
+        distance += speed * UPGRADE_INTERVAL_SECS;
+        let column = (distance / COLUMN_WIDTH) as usize;
+        if column >= count {
+            return result;
+        }
+        let tier = match generator.get_upgrade() {
+            Some(UpgradeType::Speed(upgrade)) => {
+                speed *= upgrade.modifier;
+                upgrade.tier
+            }
+            Some(upgrade) => upgrade.tier(),
+            None => UpgradeLevel::None,
+        };
+        result.push((column, tier));
+    }
+}
+
+fn render(columns: &[(f64, Column)], upgrades: &[(usize, UpgradeLevel)]) -> String {
+    let (min, max) = columns
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (height, _)| {
+            (min.min(*height), max.max(*height))
+        });
+    let margin = 20.;
+    let bottom = (max - min) * PIXELS_PER_UNIT + margin * 2.;
+    let width = columns.len() as f64 * PIXELS_PER_COLUMN;
+    let y = |height: f64| (max - height) * PIXELS_PER_UNIT + margin;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{bottom}" viewBox="0 0 {width} {bottom}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#10131a"/>"##
+    );
+    for (x, (height, column)) in columns.iter().enumerate() {
+        let left = x as f64 * PIXELS_PER_COLUMN;
+        let top = y(*height);
+        let colour = match column {
+            Column::Solid => "#9aa0a6",
+            Column::Forced => "#f39c12",
+            Column::Hole => {
+                let _ = writeln!(
+                    svg,
+                    r##"<rect x="{left}" y="{}" width="{PIXELS_PER_COLUMN}" height="4" fill="#e74c3c"/>"##,
+                    bottom - 4.
+                );
+                continue;
+            }
+        };
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{left}" y="{top}" width="{PIXELS_PER_COLUMN}" height="{}" fill="{colour}"/>"#,
+            bottom - top
+        );
+    }
+    for (column, tier) in upgrades {
+        let x = *column as f64 * PIXELS_PER_COLUMN;
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x}" y1="0" x2="{x}" y2="{bottom}" stroke="#3498db" stroke-dasharray="4 4"/><text x="{}" y="12" fill="#3498db" font-size="10">{:?}</text>"##,
+            x + 2.,
+            tier
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn main() {
+    let options = parse_options();
+    let Ok(mut generator) = Generator::from_seed_versioned(
+        options.seed,
+        options.version,
+        options.height_noise,
+        options.hole_noise,
+    ) else {
+        eprintln!("unsupported generator version {}", options.version);
+        exit(2)
+    };
+    let columns = columns(&mut generator, options.columns);
+    let upgrades = upgrade_marks(&mut generator, options.columns);
+    let holes = columns
+        .iter()
+        .filter(|(_, column)| *column == Column::Hole)
+        .count();
+    let forced = columns
+        .iter()
+        .filter(|(_, column)| *column == Column::Forced)
+        .count();
+    if let Err(err) = std::fs::write(&options.out, render(&columns, &upgrades)) {
+        eprintln!("failed to write {}: {}", options.out, err);
+        exit(1)
+    }
+    println!(
+        "{}: {} columns, {} holes, {} forced columns, {} upgrades",
+        options.out,
+        columns.len(),
+        holes,
+        forced,
+        upgrades.len()
+    );
+}
@@ -0,0 +1,194 @@
+//! Draws upgrade sequences for many seeds and reports how the upgrade pool behaves over a run.
+//!
+//! ```text
+//! cargo run --bin upgrade_sim -- [--seeds N] [--minutes M] [--interval SECS] [--falloff F]
+//! ```
+use std::process::exit;
+
+use sky_scapade::{
+    generate::{Generator, NoiseSettings, UPGRADE_WEIGHT_FALLOFF},
+    input::BASE_SPEED,
+    upgrades::{Upgrade, UpgradeLevel, UpgradeType},
+};
+use strum::{EnumCount, IntoEnumIterator};
+
+const UPGRADE_TYPES: [&str; 6] = [
+    "Speed",
+    "JumpPower",
+    "JumpSkill",
+    "DashSkill",
+    "GlideSkill",
+    "TimeSlowSkill",
+];
+
+fn type_index(upgrade: &UpgradeType) -> usize {
+    match upgrade {
+        UpgradeType::Speed(_) => 0,
+        UpgradeType::JumpPower(_) => 1,
+        UpgradeType::JumpSkill(_) => 2,
+        UpgradeType::DashSkill(_) => 3,
+        UpgradeType::GlideSkill(_) => 4,
+        UpgradeType::TimeSlowSkill(_) => 5,
+    }
+}
+
+struct Options {
+    seeds: u64,
+    minutes: u32,
+    interval: u32,
+    falloff: f64,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: upgrade_sim [--seeds N] [--minutes M] [--interval SECS] [--falloff F]");
+    exit(2)
+}
+
+fn parse_options() -> Options {
+    // Defaults match a timed run: five minutes with an upgrade every ten seconds.
+    let mut options = Options {
+        seeds: 10_000,
+        minutes: 5,
+        interval: 10,
+        falloff: UPGRADE_WEIGHT_FALLOFF,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else { usage() };
+        match flag.as_str() {
+            "--seeds" => options.seeds = value.parse().unwrap_or_else(|_| usage()),
+            "--minutes" => options.minutes = value.parse().unwrap_or_else(|_| usage()),
+            "--interval" => options.interval = value.parse().unwrap_or_else(|_| usage()),
+            "--falloff" => options.falloff = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+    // Tier weights are multiplied by the falloff, `WeightedIndex` panics on negative ones.
+    let valid_falloff = options.falloff.is_finite() && options.falloff >= 0.;
+    if options.interval == 0 || options.seeds == 0 || !valid_falloff {
+        usage()
+    }
+    options
+}
+
+#[derive(Default)]
+struct Report {
+    /// `[minute][tier]`, how many runs had reached `tier` as their best upgrade by the minute.
+    best_tier_by_minute: Vec<[u64; UpgradeLevel::COUNT]>,
+    type_counts: [u64; UPGRADE_TYPES.len()],
+    runs_with_type: [u64; UPGRADE_TYPES.len()],
+    draws: u64,
+    none_draws: u64,
+    final_speeds: Vec<f32>,
+}
+
+fn simulate(options: &Options) -> Report {
+    let mut report = Report {
+        best_tier_by_minute: vec![[0; UpgradeLevel::COUNT]; options.minutes as usize],
+        ..Default::default()
+    };
+    for seed in 0..options.seeds {
+        // Noise settings don't affect upgrades, any valid ones will do.
+        let mut generator = Generator::from_u64_seed(
+            seed,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        )
+        .with_upgrade_weight_falloff(options.falloff);
+        let mut best = UpgradeLevel::None;
+        let mut speed = BASE_SPEED;
+        let mut seen = [false; UPGRADE_TYPES.len()];
+        let mut drawn = 0;
+        for minute in 0..options.minutes as usize {
+            // Upgrades that come due by the end of this minute, so intervals that don't divide
+            // a minute still get their share.
+            let due = (minute as u32 + 1) * 60 / options.interval;
+            for _ in drawn..due {
+                report.draws += 1;
+                let Some(upgrade) = generator.get_upgrade() else {
+                    report.none_draws += 1;
+                    continue;
+                };
+                if let UpgradeType::Speed(speed_upgrade) = upgrade {
+                    speed *= speed_upgrade.modifier;
+                }
+                report.type_counts[type_index(&upgrade)] += 1;
+                seen[type_index(&upgrade)] = true;
+                if upgrade.tier() > best {
+                    best = upgrade.tier();
+                }
+            }
+            drawn = due;
+            report.best_tier_by_minute[minute][best as usize] += 1;
+        }
+        for (runs, seen) in report.runs_with_type.iter_mut().zip(seen) {
+            *runs += u64::from(seen);
+        }
+        report.final_speeds.push(speed);
+    }
+    report
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    count as f64 * 100. / total as f64
+}
+
+fn print_report(options: &Options, mut report: Report) {
+    let runs = options.seeds;
+    println!(
+        "{} seeds, {} minutes, an upgrade every {}s, tier weight falloff {}",
+        runs, options.minutes, options.interval, options.falloff
+    );
+
+    println!("\nBest tier reached by minute (% of runs)");
+    print!("{:>10}", "tier");
+    for minute in 1..=options.minutes {
+        print!("{:>7}m", minute);
+    }
+    println!();
+    for tier in UpgradeLevel::iter() {
+        let name = format!("{:?}", tier);
+        print!("{:>10}", name);
+        for minute in &report.best_tier_by_minute {
+            print!("{:>8.1}", percent(minute[tier as usize], runs));
+        }
+        println!();
+    }
+
+    println!("\nUpgrade types");
+    println!("{:>14}{:>12}{:>12}", "type", "per run", "% of runs");
+    for (i, name) in UPGRADE_TYPES.iter().enumerate() {
+        println!(
+            "{:>14}{:>12.2}{:>12.1}",
+            name,
+            report.type_counts[i] as f64 / runs as f64,
+            percent(report.runs_with_type[i], runs)
+        );
+    }
+    println!(
+        "\nNo upgrade (None sentinel or exhausted pool): {} of {} draws ({:.2}%)",
+        report.none_draws,
+        report.draws,
+        percent(report.none_draws, report.draws)
+    );
+
+    report.final_speeds.sort_unstable_by(f32::total_cmp);
+    let speeds = &report.final_speeds;
+    let mean = speeds.iter().sum::<f32>() / speeds.len() as f32;
+    let quantile = |q: f64| speeds[((speeds.len() - 1) as f64 * q) as usize];
+    println!(
+        "Final max_speed: mean {:.2}, min {:.2}, p10 {:.2}, median {:.2}, p90 {:.2}, max {:.2}",
+        mean,
+        speeds[0],
+        quantile(0.1),
+        quantile(0.5),
+        quantile(0.9),
+        speeds[speeds.len() - 1]
+    );
+}
+
+fn main() {
+    let options = parse_options();
+    let report = simulate(&options);
+    print_report(&options, report);
+}
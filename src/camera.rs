@@ -0,0 +1,101 @@
+use bevy::{
+    asset::LoadState,
+    core_pipeline::Skybox,
+    prelude::*,
+    render::render_resource::{TextureViewDescriptor, TextureViewDimension},
+};
+use bevy_rapier3d::prelude::Velocity;
+
+use crate::{input::Player, interpolate, player::spawn_player, AppState, InGameState};
+
+/// Keeps the camera on the player, widens the field of view with speed and puts up the skybox.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_skybox);
+        app.add_systems(Update, skybox_loaded);
+        app.add_systems(OnEnter(AppState::InGame), follow_player.after(spawn_player));
+        app.add_systems(OnExit(AppState::InGame), respawn_camera);
+        app.add_systems(
+            Update,
+            move_camera_based_on_speed
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+#[derive(Resource, Deref)]
+pub struct SkyboxHandle(Handle<Image>);
+
+fn load_skybox(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let skybox_handle = asset_server.load("skybox/cube.png");
+    commands.insert_resource(SkyboxHandle(skybox_handle));
+}
+
+fn skybox_loaded(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    skybox_handle: Res<SkyboxHandle>,
+    camera: Query<Entity, (With<Camera>, Without<Skybox>)>,
+) {
+    for camera_entity in camera.iter() {
+        let mut camera = commands.entity(camera_entity);
+        if asset_server.load_state(&skybox_handle.0) == LoadState::Loaded {
+            let image = images.get_mut(&skybox_handle.0).unwrap();
+            if image.texture_descriptor.array_layer_count() == 1 {
+                image.reinterpret_stacked_2d_as_array(image.height() / image.width());
+                image.texture_view_descriptor = Some(TextureViewDescriptor {
+                    dimension: Some(TextureViewDimension::Cube),
+                    ..default()
+                });
+            }
+            camera.insert(Skybox {
+                image: skybox_handle.clone(),
+                brightness: 1000.0,
+            });
+        }
+    }
+}
+
+fn follow_player(
+    mut commands: Commands,
+    mut camera: Query<(Entity, &mut Transform), With<Camera>>,
+    player: Query<Entity, With<Player>>,
+) {
+    if let (Ok((camera, mut camera_transform)), Ok(player)) =
+        (camera.get_single_mut(), player.get_single())
+    {
+        commands.entity(camera).set_parent(player);
+        *camera_transform =
+            Transform::from_xyz(0.0, 5., 20.).looking_at(Vec3::new(0., 0., 0.), Vec3::Y)
+    }
+}
+
+/// The camera is a child of the player, so it goes with the level and needs replacing.
+fn respawn_camera(mut commands: Commands) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 0.0, 0.0).looking_at(Vec3::new(0., 0.0, 0.), Vec3::Y),
+        ..default()
+    });
+}
+
+fn move_camera_based_on_speed(
+    mut query_camera: Query<(&mut Projection, &mut Transform), With<Camera>>,
+    velocities: Query<&Velocity, With<Player>>,
+) {
+    let (projection, mut transform) = query_camera.single_mut();
+    let Projection::Perspective(persp) = projection.into_inner() else {
+        return;
+    };
+    let player_velocity = velocities.single();
+    let min_fov = 45.;
+    let max_fov = 120.;
+    let fov_modifier = (player_velocity.linvel.x.abs().powf(0.125) / 8.).clamp(0., 1.);
+
+    persp.fov = interpolate(min_fov, max_fov, fov_modifier).to_radians();
+    let positive = player_velocity.linvel.x > 0.;
+    transform.translation.x =
+        (player_velocity.linvel.x.abs().sqrt() / 4.) * if positive { 1. } else { -1. };
+}
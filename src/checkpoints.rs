@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{input::Player, level::Level, player::Glider, AppState, InGameState};
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CheckpointAssets>();
+        app.add_systems(
+            Update,
+            reach_checkpoints
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+const FLAG_HEIGHT: f32 = 4.;
+
+#[derive(Resource, Clone)]
+pub struct CheckpointAssets {
+    pole_mesh: Handle<Mesh>,
+    pole_material: Handle<StandardMaterial>,
+    reached_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for CheckpointAssets {
+    fn from_world(world: &mut World) -> Self {
+        let pole_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cylinder::new(0.1, FLAG_HEIGHT));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            pole_mesh,
+            pole_material: materials.add(StandardMaterial {
+                base_color: Color::WHITE,
+                emissive: Color::WHITE,
+                ..default()
+            }),
+            reached_material: materials.add(StandardMaterial {
+                base_color: Color::GREEN,
+                emissive: Color::GREEN,
+                ..default()
+            }),
+        }
+    }
+}
+
+/// `respawn` is where the player is put back after a fall once this checkpoint is reached.
+#[derive(Component)]
+struct Checkpoint {
+    respawn: Vec3,
+}
+
+/// Spawns a checkpoint pole on the floor cube at `x`, `y`.
+pub fn spawn_checkpoint(
+    commands: &mut Commands,
+    assets: &CheckpointAssets,
+    level: Entity,
+    x: f32,
+    y: f32,
+) {
+    commands
+        .spawn((
+            Checkpoint {
+                respawn: Vec3::new(x, y + 3.5, 0.),
+            },
+            Collider::cuboid(1., FLAG_HEIGHT / 2., 1.),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            PbrBundle {
+                mesh: assets.pole_mesh.clone(),
+                material: assets.pole_material.clone(),
+                transform: Transform::from_xyz(x, y + 1. + FLAG_HEIGHT / 2., -0.8),
+                ..default()
+            },
+        ))
+        .set_parent(level);
+}
+
+fn reach_checkpoints(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    assets: Res<CheckpointAssets>,
+    checkpoints: Query<&Checkpoint>,
+    player: Query<Entity, With<Player>>,
+    mut level: Query<&mut Level>,
+) {
+    let Ok(player_entity) = player.get_single() else {
+        return;
+    };
+    let Ok(mut level) = level.get_single_mut() else {
+        return;
+    };
+    for event in collision_events.read() {
+        let CollisionEvent::Started(a, b, _) = event else {
+            continue;
+        };
+        let other = if *a == player_entity {
+            *b
+        } else if *b == player_entity {
+            *a
+        } else {
+            continue;
+        };
+        let Ok(checkpoint) = checkpoints.get(other) else {
+            continue;
+        };
+        if checkpoint.respawn.x > level.checkpoint.x {
+            level.checkpoint = checkpoint.respawn;
+            commands
+                .entity(other)
+                .insert(assets.reached_material.clone());
+        }
+    }
+}
+
+/// Puts the player back on the last reached checkpoint with a clean slate of skill charges, no
+/// glider and no speed boost.
+pub fn respawn_player(
+    commands: &mut Commands,
+    gliders: &Query<Entity, With<Glider>>,
+    level: &mut Level,
+    player: &mut Player,
+    transform: &mut Transform,
+    velocity: &mut Velocity,
+) {
+    level.respawns += 1;
+    transform.translation = level.checkpoint;
+    *velocity = Velocity::zero();
+    player.used_dashes = 0;
+    player.dash_cooldown = None;
+    player.used_glides = 0;
+    player.glide_cooldown = None;
+    player.glide_timer = None;
+    for glider in gliders.iter() {
+        commands.entity(glider).despawn_recursive();
+    }
+    player.speed_boost = None;
+    player.used_time_slows = 0;
+    player.time_slow_cooldown = None;
+    player.jump_index = 0;
+}
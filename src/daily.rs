@@ -0,0 +1,206 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy_persistent::{Persistent, StorageFormat};
+use rand::{distributions::Alphanumeric, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    generate::{Generator, NoiseSettings},
+    input::Player,
+    AppState, InGameState,
+};
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        let config_dir = crate::benchmark::profile_dir();
+        app.insert_resource(
+            DailyHistoryResource::builder()
+                .name("daily")
+                .format(StorageFormat::Toml)
+                .path(config_dir.join("daily.toml"))
+                .default(DailyHistory::default())
+                .build()
+                .expect("Failed to load daily history"),
+        );
+        app.add_systems(OnEnter(AppState::InGame), record_daily_attempt);
+        app.add_systems(OnEnter(InGameState::End), record_daily_score);
+    }
+}
+
+pub type DailyHistoryResource = Persistent<DailyHistory>;
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct DailyHistory {
+    #[serde(default)]
+    pub attempts: Vec<DailyRecord>,
+}
+impl DailyHistory {
+    pub fn played(&self, date: &str) -> bool {
+        self.attempts.iter().any(|attempt| attempt.date == date)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DailyRecord {
+    pub date: String,
+    pub seed: String,
+    #[serde(default = "crate::scores::first_generator_version")]
+    pub generator_version: u8,
+    pub score: f32,
+}
+
+/// Present while a daily challenge is being played. Only the first attempt of a day is scored,
+/// later ones are practice.
+#[derive(Resource, Clone, Debug)]
+pub struct DailyRun {
+    pub challenge: DailyChallenge,
+    pub scored: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DailyChallenge {
+    /// UTC date as `YYYY-MM-DD`, the key shared by everyone playing that day.
+    pub date: String,
+    pub seed: [u8; 32],
+    pub height_noise: (usize, f64, usize),
+    pub hole_noise: (usize, f64, usize),
+}
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            / 86_400;
+        Self::for_day(days)
+    }
+    /// Builds the challenge for a number of days since the unix epoch.
+    pub fn for_day(days: u64) -> Self {
+        let (year, month, day) = civil_from_days(days);
+        let mut rng = ChaCha20Rng::seed_from_u64(days);
+        let mut seed = [0_u8; 32];
+        for (byte, char) in seed.iter_mut().zip(rng.clone().sample_iter(&Alphanumeric)) {
+            *byte = char;
+        }
+        rng.set_stream(1);
+        Self {
+            date: format!("{:04}-{:02}-{:02}", year, month, day),
+            seed,
+            height_noise: (
+                [192, 256, 320][rng.gen_range(0..3)],
+                [48., 64., 80.][rng.gen_range(0..3)],
+                5,
+            ),
+            hole_noise: (9, 64., 3),
+        }
+    }
+    pub fn height_noise_settings(&self) -> NoiseSettings {
+        let (wave_length, amplitude, octaves) = self.height_noise;
+        NoiseSettings::new(wave_length, amplitude, octaves)
+    }
+    pub fn hole_noise_settings(&self) -> NoiseSettings {
+        let (wave_length, amplitude, octaves) = self.hole_noise;
+        NoiseSettings::new(wave_length, amplitude, octaves)
+    }
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: u64) -> (i64, u32, u32) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Records the scored attempt as soon as it starts, so leaving it early through the pause menu
+/// still uses up the day's attempt.
+fn record_daily_attempt(
+    daily: Option<ResMut<DailyRun>>,
+    mut history: ResMut<DailyHistoryResource>,
+    generator: Res<Generator>,
+) {
+    let Some(mut daily) = daily else {
+        return;
+    };
+    if !daily.scored {
+        return;
+    }
+    if history.played(&daily.challenge.date) {
+        daily.scored = false;
+        return;
+    }
+    let record = DailyRecord {
+        date: daily.challenge.date.clone(),
+        seed: crate::scores::seed_label(&daily.challenge.seed),
+        generator_version: generator.version(),
+        score: 0.,
+    };
+    info!("Recording daily challenge attempt: {:?}", record);
+    if let Err(err) = history.update(|history| history.attempts.push(record.clone())) {
+        error!("Failed to save daily history: {}", err);
+    }
+}
+
+fn record_daily_score(
+    daily: Option<Res<DailyRun>>,
+    mut history: ResMut<DailyHistoryResource>,
+    player: Query<&Player>,
+) {
+    let Some(daily) = daily else {
+        return;
+    };
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    if !daily.scored {
+        return;
+    }
+    info!("Recording daily challenge score: {}", player.score);
+    let result = history.update(|history| {
+        let date = &daily.challenge.date;
+        let attempt = history
+            .attempts
+            .iter_mut()
+            .rfind(|record| &record.date == date);
+        if let Some(record) = attempt {
+            record.score = player.score;
+        }
+    });
+    if let Err(err) = result {
+        error!("Failed to save daily history: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn dates_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+    #[test]
+    fn same_day_same_challenge() {
+        let first = DailyChallenge::for_day(20_000);
+        let second = DailyChallenge::for_day(20_000);
+        assert_eq!(first.date, second.date);
+        assert_eq!(first.seed, second.seed);
+        assert_eq!(first.height_noise, second.height_noise);
+        assert_ne!(first.seed, DailyChallenge::for_day(20_001).seed);
+    }
+}
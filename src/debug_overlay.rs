@@ -0,0 +1,200 @@
+use bevy::{
+    diagnostic::{
+        DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+    },
+    prelude::*,
+};
+use bevy_rapier3d::{prelude::Velocity, render::DebugRenderContext};
+
+use crate::{
+    hud::{progress::MPH_PER_UNIT, set_text},
+    input::Player,
+    level::Level,
+    level_diagnostics::LevelDiagnosticsPlugin,
+    system_info::SystemInformationDiagnosticsPlugin,
+    AppState,
+};
+
+/// Developer readouts in the top right corner, toggled with F3. F4 toggles the physics view
+/// while the overlay is open.
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>();
+        app.add_systems(Startup, spawn_debug_overlay);
+        app.add_systems(
+            Update,
+            (
+                toggle_debug_overlay,
+                (toggle_physics_view, diagnostics_readout).run_if(debug_overlay_visible),
+                (player_readout, level_readout)
+                    .run_if(in_state(AppState::InGame).and_then(debug_overlay_visible)),
+            ),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+pub fn debug_overlay_visible(overlay: Res<DebugOverlay>) -> bool {
+    overlay.visible
+}
+
+#[derive(Component)]
+struct DebugOverlayRoot;
+#[derive(Component)]
+struct DiagnosticsReadout;
+#[derive(Component)]
+struct LevelReadout;
+#[derive(Component)]
+struct PlayerReadout;
+
+fn spawn_debug_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.),
+                    right: Val::Px(10.),
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(6.)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.7).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            DebugOverlayRoot,
+        ))
+        .with_children(|overlay| {
+            let text_style = TextStyle {
+                color: Color::WHITE,
+                font_size: 16.0,
+                ..default()
+            };
+            overlay.spawn((
+                TextBundle::from_section("", text_style.clone()),
+                DiagnosticsReadout,
+            ));
+            overlay.spawn((
+                TextBundle::from_section("", text_style.clone()),
+                LevelReadout,
+            ));
+            overlay.spawn((TextBundle::from_section("", text_style), PlayerReadout));
+        });
+}
+
+fn toggle_debug_overlay(
+    input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut root: Query<&mut Style, With<DebugOverlayRoot>>,
+) {
+    if input.just_pressed(KeyCode::F3) {
+        overlay.visible = !overlay.visible;
+        for mut style in root.iter_mut() {
+            style.display = if overlay.visible {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
+
+/// Only does anything when the Rapier debug render plugin was added to the app.
+fn toggle_physics_view(
+    input: Res<ButtonInput<KeyCode>>,
+    debug_render: Option<ResMut<DebugRenderContext>>,
+) {
+    if let Some(mut debug_render) = debug_render {
+        if input.just_pressed(KeyCode::F4) {
+            debug_render.enabled = !debug_render.enabled;
+        }
+    }
+}
+
+fn diagnostics_readout(
+    diagnostics: Res<DiagnosticsStore>,
+    debug_render: Option<Res<DebugRenderContext>>,
+    mut readout: Query<&mut Text, With<DiagnosticsReadout>>,
+) {
+    let value = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or_default()
+    };
+    let physics_view = match debug_render {
+        Some(debug_render) if debug_render.enabled => "on",
+        Some(_) => "off",
+        None => "unavailable",
+    };
+    if let Ok(mut readout) = readout.get_single_mut() {
+        let text = format!(
+            "FPS: {:.0} ({:.2}ms)\nEntities: {:.0}\nCPU: {:.0}%  Memory: {:.0}%\nPhysics view (F4): {}",
+            value(&FrameTimeDiagnosticsPlugin::FPS),
+            value(&FrameTimeDiagnosticsPlugin::FRAME_TIME),
+            value(&EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+            value(&SystemInformationDiagnosticsPlugin::CPU_USAGE),
+            value(&SystemInformationDiagnosticsPlugin::MEM_USAGE),
+            physics_view,
+        );
+        set_text(&mut readout, text);
+    }
+}
+
+fn level_readout(
+    diagnostics: Res<DiagnosticsStore>,
+    level: Query<&Level>,
+    player: Query<&Transform, With<Player>>,
+    mut readout: Query<&mut Text, With<LevelReadout>>,
+) {
+    let (Ok(level), Ok(player)) = (level.get_single(), player.get_single()) else {
+        return;
+    };
+    let value = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap_or_default()
+    };
+    if let Ok(mut readout) = readout.get_single_mut() {
+        // Columns are two world units wide.
+        let text = format!(
+            "Colliders: {:.0}  Chunks: {:.0}\nGenerated to column: {}\nCurrent column: {}\nNext upgrade: {:.1}s",
+            value(&LevelDiagnosticsPlugin::COLLIDERS),
+            value(&LevelDiagnosticsPlugin::CHUNKS),
+            level.right,
+            (player.translation.x / 2.).max(0.) as usize,
+            level.upgrade_timer.remaining_secs(),
+        );
+        set_text(&mut readout, text);
+    }
+}
+
+fn player_readout(
+    player: Query<(&Transform, &Velocity), With<Player>>,
+    mut readout: Query<&mut Text, With<PlayerReadout>>,
+) {
+    let Ok((player_transform, velocity)) = player.get_single() else {
+        return;
+    };
+    if let Ok(mut readout) = readout.get_single_mut() {
+        let position = format!(
+            "Position: [{:+5.1},{:+5.1},{:+5.1}]\nVelocity: [{:+5.1}mph,{:+5.1}mph,{:+5.1}mph]",
+            player_transform.translation.x,
+            player_transform.translation.y,
+            player_transform.translation.z,
+            velocity.linvel.x * MPH_PER_UNIT,
+            velocity.linvel.y * MPH_PER_UNIT,
+            velocity.linvel.z * MPH_PER_UNIT
+        );
+        set_text(&mut readout, position);
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use bevy_diagnostic::{DiagnosticPath, DiagnosticsStore};
+
+use crate::{generate::Generator, AppState, InGameState};
+
+/// Samples every registered diagnostic while a run is being played and writes them to a CSV
+/// file per run, enabled with `--record-diagnostics`.
+pub struct DiagnosticsRecorderPlugin;
+
+impl Plugin for DiagnosticsRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        if std::env::args().any(|arg| arg == "--record-diagnostics") {
+            app.init_resource::<RecordDiagnostics>();
+        }
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            start_recording.run_if(resource_exists::<RecordDiagnostics>),
+        );
+        app.add_systems(
+            Update,
+            sample_diagnostics.run_if(
+                resource_exists::<DiagnosticsRecording>
+                    .and_then(in_state(AppState::InGame))
+                    .and_then(in_state(InGameState::Playing)),
+            ),
+        );
+        app.add_systems(OnExit(AppState::InGame), finish_recording);
+    }
+}
+
+/// Runs started while this exists are recorded.
+#[derive(Resource)]
+pub struct RecordDiagnostics {
+    /// Real time between two rows.
+    pub interval: Duration,
+}
+impl Default for RecordDiagnostics {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+        }
+    }
+}
+
+/// The CSV of the run being played.
+#[derive(Resource)]
+pub struct DiagnosticsRecording {
+    path: PathBuf,
+    file: BufWriter<File>,
+    columns: Vec<DiagnosticPath>,
+    seed: String,
+    timer: Timer,
+    elapsed: Duration,
+}
+
+pub fn diagnostics_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap()
+        .join(env!("CARGO_PKG_NAME"))
+        .join("diagnostics")
+}
+
+fn start_recording(
+    mut commands: Commands,
+    settings: Res<RecordDiagnostics>,
+    diagnostics: Res<DiagnosticsStore>,
+    generator: Res<Generator>,
+) {
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let seed = crate::scores::seed_label(&generator.get_seed());
+    let path = diagnostics_dir().join(recording_file_name(started, &seed));
+    let mut columns: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_enabled)
+        .map(|diagnostic| diagnostic.path().clone())
+        .collect();
+    columns.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    let file = fs::create_dir_all(diagnostics_dir()).and_then(|_| File::create(&path));
+    let mut file = match file {
+        Ok(file) => BufWriter::new(file),
+        Err(err) => {
+            error!("Failed to start recording diagnostics: {}", err);
+            return;
+        }
+    };
+    let header = csv_row(
+        ["seconds", "version", "generator_version", "seed"]
+            .into_iter()
+            .map(String::from)
+            .chain(columns.iter().map(|path| path.as_str().to_string())),
+    );
+    if let Err(err) = writeln!(file, "{}", header) {
+        error!("Failed to start recording diagnostics: {}", err);
+        return;
+    }
+    commands.insert_resource(DiagnosticsRecording {
+        path,
+        file,
+        columns,
+        seed,
+        timer: Timer::new(settings.interval, TimerMode::Repeating),
+        elapsed: Duration::ZERO,
+    });
+}
+
+fn sample_diagnostics(
+    mut recording: ResMut<DiagnosticsRecording>,
+    diagnostics: Res<DiagnosticsStore>,
+    generator: Res<Generator>,
+    time: Res<Time<Real>>,
+) {
+    recording.elapsed += time.delta();
+    if !recording.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let row = csv_row(
+        [
+            format!("{:.3}", recording.elapsed.as_secs_f64()),
+            env!("CARGO_PKG_VERSION").to_string(),
+            generator.version().to_string(),
+            recording.seed.clone(),
+        ]
+        .into_iter()
+        .chain(recording.columns.iter().map(|path| {
+            // Averaged over the diagnostic's history so spikes between samples still show up.
+            diagnostics
+                .get(path)
+                .and_then(|diagnostic| diagnostic.average())
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        })),
+    );
+    if let Err(err) = writeln!(recording.file, "{}", row) {
+        error!("Failed to record diagnostics: {}", err);
+    }
+}
+
+fn finish_recording(mut commands: Commands, recording: Option<ResMut<DiagnosticsRecording>>) {
+    let Some(mut recording) = recording else {
+        return;
+    };
+    match recording.file.flush() {
+        Ok(()) => info!("Diagnostics recorded to {}", recording.path.display()),
+        Err(err) => error!("Failed to record diagnostics: {}", err),
+    }
+    commands.remove_resource::<DiagnosticsRecording>();
+}
+
+/// Milliseconds since the unix epoch and the seed, with anything that can't go into a file name
+/// replaced.
+fn recording_file_name(started: u128, seed: &str) -> String {
+    let seed: String = seed
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}.csv", started, seed)
+}
+
+/// Joins `fields` with commas, quoting the ones that need it.
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn csv_quotes_seeds() {
+        let row = csv_row(["1.5", "my, \"seed\"", ""].map(String::from));
+        assert_eq!(row, "1.5,\"my, \"\"seed\"\"\",");
+    }
+    #[test]
+    fn file_name_has_seed() {
+        assert_eq!(
+            recording_file_name(1_700_000_000_123, "my/seed 1"),
+            "1700000000123-my_seed_1.csv"
+        );
+    }
+}
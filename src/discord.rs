@@ -1,55 +1,133 @@
+#[cfg(feature = "discord")]
+use std::{
+    sync::mpsc::{self, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "discord")]
+use bevy::app::AppExit;
 use bevy::prelude::*;
-use discord_rich_presence::{activity::{self, Timestamps}, DiscordIpc, DiscordIpcClient};
+
+#[cfg(feature = "discord")]
+use crate::settings::SettingsResource;
+#[cfg(feature = "discord")]
+use presence::{PresenceMessage, PresenceTiming};
+
+#[cfg(feature = "discord")]
+mod ipc;
+#[cfg(feature = "discord")]
+mod presence;
+
+/// Mirrors `ActivityState` to Discord rich presence. Without the `discord` feature only the
+/// resource is kept, so the rest of the game can keep updating it.
 pub struct DiscordPlugin;
-#[derive(Resource, Deref, DerefMut)]
-pub struct DiscordClient(Option<DiscordIpcClient>);
+
+/// Compiled in from `DISCORD_CLIENT_ID` at build time, see `build.rs`.
+#[cfg(feature = "discord")]
+const FALLBACK_CLIENT_ID: Option<&str> = option_env!("DISCORD_CLIENT_ID");
+/// How long quitting waits for the presence thread, which can be stuck on an unresponsive Discord.
+#[cfg(feature = "discord")]
+const CLOSE_TIMEOUT: Duration = Duration::from_millis(500);
 
 impl Plugin for DiscordPlugin {
     fn build(&self, app: &mut App) {
-        let client = DiscordIpcClient::new(env!("DISCORD_CLIENT_ID")).unwrap();
         app.init_resource::<ActivityState>();
-        app.insert_resource(DiscordClient(Some(client)));
-        app.add_systems(Startup, startup_client);
-        app.add_systems(FixedUpdate, check_activity_changed);
+        #[cfg(feature = "discord")]
+        {
+            app.add_systems(Startup, start_presence);
+            app.add_systems(
+                FixedUpdate,
+                check_activity_changed.run_if(resource_exists::<DiscordPresence>),
+            );
+            app.add_systems(Last, close_presence.run_if(on_event::<AppExit>()));
+        }
     }
 }
-fn startup_client(mut client: ResMut<DiscordClient>) {
-    match &mut client.0{
-        Some(discord) =>{
-            if let Err(err) = discord.connect(){
-                error!("{}",err);
-                client.0 = None;
-            }
-        },
-        None => {},
+
+/// Hands activity updates to the thread that talks to Discord.
+#[cfg(feature = "discord")]
+#[derive(Resource)]
+pub struct DiscordPresence {
+    messages: Sender<PresenceMessage>,
+    worker: Option<JoinHandle<()>>,
+}
+
+/// The `DISCORD_CLIENT_ID` environment variable wins over the settings file, which wins over the
+/// ID compiled into the build.
+#[cfg(feature = "discord")]
+fn resolve_client_id(
+    env: Option<String>,
+    setting: Option<&str>,
+    fallback: Option<&str>,
+) -> Option<String> {
+    env.filter(|id| !id.is_empty())
+        .or_else(|| setting.filter(|id| !id.is_empty()).map(String::from))
+        .or_else(|| fallback.filter(|id| !id.is_empty()).map(String::from))
+}
+
+#[cfg(feature = "discord")]
+fn start_presence(mut commands: Commands, settings: Option<Res<SettingsResource>>) {
+    let Some(client_id) = resolve_client_id(
+        std::env::var("DISCORD_CLIENT_ID").ok(),
+        settings
+            .as_ref()
+            .and_then(|settings| settings.discord_client_id.as_deref()),
+        FALLBACK_CLIENT_ID,
+    ) else {
+        info!("No Discord client ID configured, rich presence is off");
+        return;
+    };
+    let (messages, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .name("discord-presence".into())
+        .spawn(move || {
+            presence::run(
+                &client_id,
+                ipc::connect_local,
+                receiver,
+                PresenceTiming::default(),
+            )
+        });
+    match worker {
+        Ok(worker) => commands.insert_resource(DiscordPresence {
+            messages,
+            worker: Some(worker),
+        }),
+        Err(err) => error!("Failed to start Discord presence: {}", err),
     }
-    
-}
-fn check_activity_changed(activity: Res<ActivityState>, mut client: ResMut<DiscordClient>) {
-    match &mut client.0{
-        Some(client) => {
-            if activity.is_changed() {
-                let mut discord_activity: activity::Activity = activity::Activity::new();
-                if let Some(state) = &activity.state {
-                    discord_activity = discord_activity.state(state);
-                }
-                if let Some(details) = &activity.details {
-                    discord_activity = discord_activity.details(details);
-                }
-                if let Some(start) = &activity.start{
-                    discord_activity = discord_activity.timestamps(Timestamps::new().start(*start));
-                }
-                let res = client.set_activity(discord_activity);
-        
-                if let Err(why) = res {
-                    error!("Failed to set presence: {}", why);
-                }
-            }
-        },
-        None => {},
+}
+
+#[cfg(feature = "discord")]
+fn check_activity_changed(activity: Res<ActivityState>, presence: Res<DiscordPresence>) {
+    if activity.is_changed() {
+        let _ = presence
+            .messages
+            .send(PresenceMessage::Update(activity.clone()));
+    }
+}
+
+/// Clears the presence before the game goes away instead of leaving it to Discord's timeout.
+#[cfg(feature = "discord")]
+fn close_presence(presence: Option<ResMut<DiscordPresence>>) {
+    let Some(mut presence) = presence else {
+        return;
+    };
+    let _ = presence.messages.send(PresenceMessage::Close);
+    let Some(worker) = presence.worker.take() else {
+        return;
+    };
+    let deadline = Instant::now() + CLOSE_TIMEOUT;
+    while !worker.is_finished() {
+        if Instant::now() >= deadline {
+            warn!("Discord presence didn't close in time, leaving it behind");
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
     }
-    
+    let _ = worker.join();
 }
+
 #[derive(Debug, Resource, Default, Clone)]
 pub struct ActivityState {
     /// The player's current party status
@@ -59,3 +137,25 @@ pub struct ActivityState {
     /// Start time of Activity
     pub start: Option<i64>,
 }
+
+#[cfg(all(test, feature = "discord"))]
+mod tests {
+    use super::*;
+    #[test]
+    fn client_id_precedence() {
+        let env = || Some("env".to_string());
+        assert_eq!(
+            resolve_client_id(env(), Some("settings"), Some("built")),
+            env()
+        );
+        assert_eq!(
+            resolve_client_id(Some(String::new()), Some("settings"), Some("built")).as_deref(),
+            Some("settings")
+        );
+        assert_eq!(
+            resolve_client_id(None, None, Some("built")).as_deref(),
+            Some("built")
+        );
+        assert_eq!(resolve_client_id(None, Some(""), None), None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Discord's local IPC protocol: frames of a little endian opcode and length followed by JSON.
+use std::io::{self, Read, Write};
+
+use serde_json::{json, Value};
+
+use super::ActivityState;
+
+const HANDSHAKE: u32 = 0;
+const FRAME: u32 = 1;
+const CLOSE: u32 = 2;
+const PING: u32 = 3;
+const PONG: u32 = 4;
+/// Discord's replies are a few hundred bytes, anything this large is not Discord.
+const MAX_FRAME_LEN: u32 = 64 * 1024;
+/// How long a connected client waits on Discord before giving up on the connection.
+#[cfg(unix)]
+const IO_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[cfg(unix)]
+pub type IpcStream = std::os::unix::net::UnixStream;
+#[cfg(windows)]
+pub type IpcStream = std::fs::File;
+
+/// Connects to the first of the `discord-ipc-0` to `discord-ipc-9` sockets a running Discord
+/// client listens on, including the ones of the Flatpak and Snap packages.
+#[cfg(unix)]
+pub fn connect_local() -> io::Result<IpcStream> {
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map_or_else(|| "/tmp".into(), std::path::PathBuf::from);
+    for package in ["", "app/com.discordapp.Discord", "snap.discord"] {
+        for index in 0..10 {
+            let path = dir.join(package).join(format!("discord-ipc-{}", index));
+            if let Ok(stream) = IpcStream::connect(path) {
+                stream.set_read_timeout(Some(IO_TIMEOUT))?;
+                stream.set_write_timeout(Some(IO_TIMEOUT))?;
+                return Ok(stream);
+            }
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Discord is not running",
+    ))
+}
+
+#[cfg(windows)]
+pub fn connect_local() -> io::Result<IpcStream> {
+    for index in 0..10 {
+        let pipe = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\?\pipe\discord-ipc-{}", index));
+        if let Ok(pipe) = pipe {
+            return Ok(pipe);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Discord is not running",
+    ))
+}
+
+/// A handshaken connection to Discord.
+pub struct IpcClient<S> {
+    stream: S,
+    nonce: u64,
+}
+
+impl<S: Read + Write> IpcClient<S> {
+    /// Identifies as `client_id` and waits for Discord to be ready.
+    pub fn handshake(mut stream: S, client_id: &str) -> io::Result<Self> {
+        write_frame(
+            &mut stream,
+            HANDSHAKE,
+            &json!({ "v": 1, "client_id": client_id }),
+        )?;
+        let mut client = Self { stream, nonce: 0 };
+        client.read_reply()?;
+        Ok(client)
+    }
+
+    pub fn set_activity(&mut self, activity: &ActivityState) -> io::Result<()> {
+        self.nonce += 1;
+        let command = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": {
+                "pid": std::process::id(),
+                "activity": activity_json(activity),
+            },
+            "nonce": self.nonce.to_string(),
+        });
+        write_frame(&mut self.stream, FRAME, &command)?;
+        self.read_reply().map(|_| ())
+    }
+
+    /// Tells Discord we are leaving, so the presence disappears right away.
+    pub fn close(mut self) {
+        let _ = write_frame(&mut self.stream, CLOSE, &json!({}));
+    }
+
+    /// Reads frames until the reply to the last command, answering pings on the way.
+    fn read_reply(&mut self) -> io::Result<Value> {
+        loop {
+            let (op, payload) = read_frame(&mut self.stream)?;
+            match op {
+                FRAME if payload["evt"] == "ERROR" => {
+                    return Err(io::Error::other(format!(
+                        "Discord rejected the command: {}",
+                        payload["data"]["message"]
+                    )));
+                }
+                FRAME => return Ok(payload),
+                PING => write_frame(&mut self.stream, PONG, &payload)?,
+                CLOSE => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        format!("Discord closed the connection: {}", payload["message"]),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Only the fields that are set, no activity at all clears the presence.
+fn activity_json(activity: &ActivityState) -> Value {
+    if activity.state.is_none() && activity.details.is_none() && activity.start.is_none() {
+        return Value::Null;
+    }
+    let mut json = json!({});
+    if let Some(state) = &activity.state {
+        json["state"] = state.as_str().into();
+    }
+    if let Some(details) = &activity.details {
+        json["details"] = details.as_str().into();
+    }
+    if let Some(start) = activity.start {
+        json["timestamps"] = json!({ "start": start });
+    }
+    json
+}
+
+pub fn write_frame(stream: &mut impl Write, op: u32, payload: &Value) -> io::Result<()> {
+    let payload = serde_json::to_vec(payload)?;
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    stream.write_all(&frame)?;
+    stream.flush()
+}
+
+pub fn read_frame(stream: &mut impl Read) -> io::Result<(u32, Value)> {
+    let mut header = [0; 8];
+    stream.read_exact(&mut header)?;
+    let op = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("IPC frame of {} bytes", len),
+        ));
+    }
+    let mut payload = vec![0; len as usize];
+    stream.read_exact(&mut payload)?;
+    Ok((op, serde_json::from_slice(&payload)?))
+}
@@ -0,0 +1,223 @@
+//! The background thread that owns the Discord connection, so a missing or slow Discord never
+//! stalls a frame.
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+
+use super::{ipc::IpcClient, ActivityState};
+
+pub enum PresenceMessage {
+    Update(ActivityState),
+    Close,
+}
+
+pub struct PresenceTiming {
+    /// Discord accepts five activity updates per 20 seconds, anything in between is coalesced
+    /// into the latest state.
+    pub min_update_interval: Duration,
+    /// Wait before the first reconnect, doubled after every failed attempt.
+    pub first_retry: Duration,
+    pub max_retry: Duration,
+}
+impl Default for PresenceTiming {
+    fn default() -> Self {
+        Self {
+            min_update_interval: Duration::from_secs(4),
+            first_retry: Duration::from_secs(2),
+            max_retry: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Keeps Discord showing the latest `ActivityState` until `Close` arrives or the sender is
+/// dropped, reconnecting with `connect` whenever Discord goes away.
+pub fn run<S: Read + Write>(
+    client_id: &str,
+    mut connect: impl FnMut() -> io::Result<S>,
+    messages: Receiver<PresenceMessage>,
+    timing: PresenceTiming,
+) {
+    let mut connection: Option<IpcClient<S>> = None;
+    let mut latest: Option<ActivityState> = None;
+    // Whether `latest` has been shown on the current connection.
+    let mut shown = true;
+    let mut last_update: Option<Instant> = None;
+    let mut retry = timing.first_retry;
+    let mut next_attempt = Instant::now();
+    loop {
+        let now = Instant::now();
+        if connection.is_none() && now >= next_attempt {
+            match connect().and_then(|stream| IpcClient::handshake(stream, client_id)) {
+                Ok(client) => {
+                    info!("Connected to Discord");
+                    connection = Some(client);
+                    retry = timing.first_retry;
+                    shown = latest.is_none();
+                }
+                Err(err) => {
+                    debug!("Discord unavailable, retrying in {:?}: {}", retry, err);
+                    next_attempt = now + retry;
+                    retry = (retry * 2).min(timing.max_retry);
+                }
+            }
+        }
+        let update_due = last_update.map_or(now, |last| last + timing.min_update_interval);
+        if let (Some(client), Some(activity)) = (&mut connection, &latest) {
+            if !shown && now >= update_due {
+                match client.set_activity(activity) {
+                    Ok(()) => {
+                        shown = true;
+                        last_update = Some(now);
+                    }
+                    Err(err) => {
+                        warn!("Lost connection to Discord: {}", err);
+                        connection = None;
+                        next_attempt = now + retry;
+                    }
+                }
+            }
+        }
+
+        let wake = match &connection {
+            None => Some(next_attempt),
+            Some(_) if !shown => Some(update_due),
+            Some(_) => None,
+        };
+        let message = match wake {
+            Some(wake) => {
+                match messages.recv_timeout(wake.saturating_duration_since(Instant::now())) {
+                    Ok(message) => message,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => PresenceMessage::Close,
+                }
+            }
+            None => messages.recv().unwrap_or(PresenceMessage::Close),
+        };
+        match message {
+            PresenceMessage::Update(activity) => {
+                latest = Some(activity);
+                shown = false;
+            }
+            PresenceMessage::Close => {
+                if let Some(client) = connection {
+                    client.close();
+                }
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        os::unix::net::{UnixListener, UnixStream},
+        path::PathBuf,
+        sync::mpsc::{self, Sender},
+        thread::{self, JoinHandle},
+    };
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::discord::ipc::{read_frame, write_frame};
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "{}-{}-{}",
+            env!("CARGO_PKG_NAME"),
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn start(path: PathBuf) -> (Sender<PresenceMessage>, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let timing = PresenceTiming {
+                min_update_interval: Duration::from_millis(300),
+                first_retry: Duration::from_millis(10),
+                max_retry: Duration::from_millis(50),
+            };
+            run("1234", || UnixStream::connect(&path), receiver, timing);
+        });
+        (sender, worker)
+    }
+
+    fn update(details: &str) -> PresenceMessage {
+        PresenceMessage::Update(ActivityState {
+            details: Some(details.into()),
+            ..default()
+        })
+    }
+
+    /// Accepts the next connection the way Discord does.
+    fn accept(listener: &UnixListener) -> UnixStream {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let (op, handshake) = read_frame(&mut stream).unwrap();
+        assert_eq!(op, 0);
+        assert_eq!(handshake["client_id"], "1234");
+        write_frame(
+            &mut stream,
+            1,
+            &json!({ "cmd": "DISPATCH", "evt": "READY" }),
+        )
+        .unwrap();
+        stream
+    }
+
+    fn next_details(stream: &mut UnixStream) -> Value {
+        let (op, command) = read_frame(stream).unwrap();
+        assert_eq!(op, 1);
+        assert_eq!(command["cmd"], "SET_ACTIVITY");
+        let reply = json!({ "cmd": "SET_ACTIVITY", "evt": null, "nonce": command["nonce"] });
+        write_frame(stream, 1, &reply).unwrap();
+        command["args"]["activity"]["details"].clone()
+    }
+
+    #[test]
+    fn coalesces_updates_and_closes() {
+        let path = socket_path("coalesce");
+        let listener = UnixListener::bind(&path).unwrap();
+        let (sender, worker) = start(path.clone());
+        sender.send(update("first")).unwrap();
+        let mut discord = accept(&listener);
+        assert_eq!(next_details(&mut discord), "first");
+        for details in ["second", "third", "fourth"] {
+            sender.send(update(details)).unwrap();
+        }
+        assert_eq!(next_details(&mut discord), "fourth");
+        sender.send(PresenceMessage::Close).unwrap();
+        assert_eq!(read_frame(&mut discord).unwrap().0, 2);
+        worker.join().unwrap();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn reconnects_when_discord_comes_back() {
+        let path = socket_path("reconnect");
+        let (sender, worker) = start(path.clone());
+        sender.send(update("waiting")).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        let listener = UnixListener::bind(&path).unwrap();
+        let mut discord = accept(&listener);
+        assert_eq!(next_details(&mut discord), "waiting");
+
+        drop(discord);
+        sender.send(update("again")).unwrap();
+        let mut discord = accept(&listener);
+        assert_eq!(next_details(&mut discord), "again");
+        drop(sender);
+        worker.join().unwrap();
+        let _ = std::fs::remove_file(path);
+    }
+}
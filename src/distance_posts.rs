@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{events::ChunkGenerated, generate::Generator, level::Level, AppState};
+
+pub struct DistancePostPlugin;
+
+impl Plugin for DistancePostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DistancePostAssets>();
+        app.add_systems(
+            Update,
+            spawn_distance_posts.run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// World units between two distance posts.
+pub const DISTANCE_POST_SPACING: f32 = 1000.;
+const POST_HEIGHT: f32 = 8.;
+
+#[derive(Resource, Clone)]
+pub struct DistancePostAssets {
+    pole_mesh: Handle<Mesh>,
+    sign_mesh: Handle<Mesh>,
+    pole_material: Handle<StandardMaterial>,
+    sign_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for DistancePostAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let pole_mesh = meshes.add(Cylinder::new(0.15, POST_HEIGHT));
+        let sign_mesh = meshes.add(Cuboid::new(2.5, 1., 0.1));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            pole_mesh,
+            sign_mesh,
+            pole_material: materials.add(StandardMaterial {
+                base_color: Color::GRAY,
+                ..default()
+            }),
+            sign_material: materials.add(StandardMaterial {
+                base_color: Color::CYAN,
+                emissive: Color::CYAN,
+                ..default()
+            }),
+        }
+    }
+}
+
+/// Marks `distance` along the track, behind the floor so it never blocks the player.
+#[derive(Component)]
+pub struct DistancePost {
+    pub distance: f32,
+}
+
+fn spawn_distance_posts(
+    mut commands: Commands,
+    mut chunks: EventReader<ChunkGenerated>,
+    assets: Res<DistancePostAssets>,
+    level: Query<Entity, With<Level>>,
+    mut generator: ResMut<Generator>,
+) {
+    let Ok(level) = level.get_single() else {
+        return;
+    };
+    for chunk in chunks.read() {
+        // Columns are two world units wide.
+        let start = ((chunk.start * 2) as f32 / DISTANCE_POST_SPACING)
+            .ceil()
+            .max(1.) as usize;
+        let end = ((chunk.end * 2) as f32 / DISTANCE_POST_SPACING).ceil() as usize;
+        for post in start..end {
+            let distance = post as f32 * DISTANCE_POST_SPACING;
+            let floor = generator.get_height((distance / 2.) as usize) as f32 + 1.;
+            commands
+                .spawn((
+                    DistancePost { distance },
+                    PbrBundle {
+                        mesh: assets.pole_mesh.clone(),
+                        material: assets.pole_material.clone(),
+                        transform: Transform::from_xyz(distance, floor + POST_HEIGHT / 2., -2.),
+                        ..default()
+                    },
+                ))
+                .with_children(|post| {
+                    post.spawn(PbrBundle {
+                        mesh: assets.sign_mesh.clone(),
+                        material: assets.sign_material.clone(),
+                        transform: Transform::from_xyz(0., POST_HEIGHT / 2. - 0.5, 0.2),
+                        ..default()
+                    });
+                })
+                .set_parent(level);
+        }
+    }
+}
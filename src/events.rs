@@ -0,0 +1,91 @@
+//! Gameplay events sent by the core systems, so features like stats, achievements and presence
+//! can react without polling or touching gameplay code.
+use bevy::prelude::*;
+
+use crate::{input::Player, level::Level, upgrades::UpgradeType, InGameState};
+
+pub struct GameplayEventsPlugin;
+
+impl Plugin for GameplayEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<UpgradeAcquired>()
+            .add_event::<SkillUsed>()
+            .add_event::<PlayerLanded>()
+            .add_event::<PlayerDied>()
+            .add_event::<RunFinished>()
+            .add_event::<ChunkGenerated>();
+        app.add_systems(OnEnter(InGameState::End), run_finished);
+    }
+}
+
+/// An upgrade was rolled and applied to the player, from the upgrade timer or a crate.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UpgradeAcquired(pub UpgradeType);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Skill {
+    Jump,
+    /// `index` is 1 for the first jump in the air, 2 for the second and so on.
+    AirJump {
+        index: usize,
+    },
+    Dash,
+    Glide,
+    TimeSlow,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SkillUsed(pub Skill);
+
+/// The player touched the ground after being airborne.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerLanded {
+    pub position: Vec3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeathCause {
+    Fall,
+    Spikes,
+    ChaseWall,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerDied {
+    pub cause: DeathCause,
+    pub position: Vec3,
+    /// Whether the run goes on from a checkpoint.
+    pub respawned: bool,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RunFinished {
+    pub score: f32,
+    pub distance: f32,
+    /// The run timer ran out, as opposed to the player dying.
+    pub timed_out: bool,
+}
+
+/// Floor columns `start..end` were spawned.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ChunkGenerated {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Every path that ends a run goes through `InGameState::End`.
+fn run_finished(
+    player: Query<&Player>,
+    level: Query<&Level>,
+    mode: Res<crate::game_mode::GameMode>,
+    mut finished: EventWriter<RunFinished>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    finished.send(RunFinished {
+        score: player.score,
+        distance: player.distance,
+        timed_out: mode.is_timed() && level.get_single().is_ok_and(|level| level.timer.finished()),
+    });
+}
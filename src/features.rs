@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    generate::Feature,
+    hazards::FLOOR_SENSOR_HEIGHT,
+    input::{Player, SpeedBoost},
+    upgrades::{StatUpgrade, UpgradeLevel},
+    AppState, InGameState,
+};
+
+pub struct FeaturePlugin;
+
+impl Plugin for FeaturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FeatureAssets>();
+        app.add_systems(
+            Update,
+            (feature_collisions, speed_boost, wind_zones)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+/// Boost pads multiply the player's speed by this while the boost lasts.
+const BOOST_PAD_MODIFIER: f32 = 1.5;
+const BOOST_PAD_DURATION: Duration = Duration::from_secs(3);
+const WIND_ACCELERATION: f32 = 30.;
+const WIND_MAX_RISE: f32 = 4.;
+
+#[derive(Resource, Clone)]
+pub struct FeatureAssets {
+    boost_pad_mesh: Handle<Mesh>,
+    boost_pad_material: Handle<StandardMaterial>,
+    spring_mesh: Handle<Mesh>,
+    spring_material: Handle<StandardMaterial>,
+    wind_mesh: Handle<Mesh>,
+    wind_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for FeatureAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let boost_pad_mesh = meshes.add(Cuboid::new(1.8, 0.1, 1.8));
+        let spring_mesh = meshes.add(Cylinder::new(0.6, 0.4));
+        let wind_mesh = meshes.add(Cuboid::new(1., 1., 1.));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            boost_pad_mesh,
+            boost_pad_material: materials.add(StandardMaterial {
+                base_color: Color::YELLOW,
+                emissive: Color::YELLOW,
+                ..default()
+            }),
+            spring_mesh,
+            spring_material: materials.add(StandardMaterial {
+                base_color: Color::LIME_GREEN,
+                ..default()
+            }),
+            wind_mesh,
+            wind_material: materials.add(StandardMaterial {
+                base_color: Color::rgba(0.6, 0.9, 1., 0.15),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            }),
+        }
+    }
+}
+
+#[derive(Component)]
+struct FeatureSensor(Feature);
+
+#[derive(Component)]
+struct WindZone;
+
+/// Spawns the feature for a column on top of the floor cube at `x`, `y`.
+pub fn spawn_feature(
+    commands: &mut Commands,
+    assets: &FeatureAssets,
+    level: Entity,
+    feature: Feature,
+    x: f32,
+    y: f32,
+) {
+    match feature {
+        Feature::BoostPad => {
+            commands
+                .spawn(PbrBundle {
+                    mesh: assets.boost_pad_mesh.clone(),
+                    material: assets.boost_pad_material.clone(),
+                    transform: Transform::from_xyz(x, y + 1.05, 0.),
+                    ..default()
+                })
+                .with_children(|pad| {
+                    pad.spawn((
+                        FeatureSensor(feature),
+                        Collider::cuboid(0.9, FLOOR_SENSOR_HEIGHT / 2., 0.9),
+                        floor_sensor(0.05),
+                    ));
+                })
+                .set_parent(level);
+        }
+        Feature::Spring { .. } => {
+            commands
+                .spawn(PbrBundle {
+                    mesh: assets.spring_mesh.clone(),
+                    material: assets.spring_material.clone(),
+                    transform: Transform::from_xyz(x, y + 1.2, 0.),
+                    ..default()
+                })
+                .with_children(|spring| {
+                    spring.spawn((
+                        FeatureSensor(feature),
+                        Collider::cylinder(FLOOR_SENSOR_HEIGHT / 2., 0.6),
+                        floor_sensor(0.2),
+                    ));
+                })
+                .set_parent(level);
+        }
+        Feature::WindZone { width } => {
+            // Columns are two units apart, so the holes after `x` span `2 * width` units.
+            let half_width = width as f32;
+            let bottom = y - 10.;
+            let top = y + 15.;
+            commands
+                .spawn((
+                    FeatureSensor(feature),
+                    WindZone,
+                    Collider::cuboid(half_width, (top - bottom) / 2., 1.),
+                    Sensor,
+                    PbrBundle {
+                        mesh: assets.wind_mesh.clone(),
+                        material: assets.wind_material.clone(),
+                        transform: Transform::from_xyz(
+                            x + 1. + half_width,
+                            (top + bottom) / 2.,
+                            0.,
+                        )
+                        .with_scale(Vec3::new(half_width * 2., top - bottom, 2.)),
+                        ..default()
+                    },
+                ))
+                .set_parent(level);
+        }
+    }
+}
+
+/// Places a sensor under a feature whose center is `center` above the floor, so it reaches from
+/// the floor into a player standing on the feature.
+fn floor_sensor(center: f32) -> impl Bundle {
+    (
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        TransformBundle::from_transform(Transform::from_xyz(
+            0.,
+            FLOOR_SENSOR_HEIGHT / 2. - center,
+            0.,
+        )),
+    )
+}
+
+fn feature_collisions(
+    mut collision_events: EventReader<CollisionEvent>,
+    sensors: Query<&FeatureSensor>,
+    mut player: Query<(Entity, &mut Player, &mut Velocity)>,
+    rapier_config: Res<RapierConfiguration>,
+) {
+    let Ok((player_entity, mut player, mut velocity)) = player.get_single_mut() else {
+        return;
+    };
+    for event in collision_events.read() {
+        let CollisionEvent::Started(a, b, _) = event else {
+            continue;
+        };
+        let other = if *a == player_entity {
+            *b
+        } else if *b == player_entity {
+            *a
+        } else {
+            continue;
+        };
+        let Ok(sensor) = sensors.get(other) else {
+            continue;
+        };
+        match sensor.0 {
+            Feature::BoostPad => {
+                player.speed_boost = Some(SpeedBoost {
+                    upgrade: StatUpgrade {
+                        modifier: BOOST_PAD_MODIFIER,
+                        additive: false,
+                        tier: UpgradeLevel::None,
+                    },
+                    timer: Timer::new(BOOST_PAD_DURATION, TimerMode::Once),
+                });
+            }
+            Feature::Spring { height } => {
+                // v = sqrt(2gh), with a little headroom above the cliff.
+                let gravity = -rapier_config.gravity.y;
+                velocity.linvel.y = (2. * gravity * (height + 3.)).sqrt();
+            }
+            Feature::WindZone { .. } => {}
+        }
+    }
+}
+
+fn speed_boost(mut player: Query<&mut Player>, time: Res<Time>) {
+    let mut player = player.single_mut();
+    let finished = match &mut player.speed_boost {
+        Some(boost) => boost.timer.tick(time.delta()).finished(),
+        None => false,
+    };
+    if finished {
+        player.speed_boost = None;
+    }
+}
+
+fn wind_zones(
+    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+    zones: Query<Entity, With<WindZone>>,
+    mut player: Query<(Entity, &Player, &mut Velocity)>,
+) {
+    let Ok((player_entity, player, mut velocity)) = player.get_single_mut() else {
+        return;
+    };
+    let in_wind = zones
+        .iter()
+        .any(|zone| rapier_context.intersection_pair(player_entity, zone) == Some(true));
+    if in_wind && velocity.linvel.y < WIND_MAX_RISE {
+        // Gliding catches the updraft far better than falling does.
+        let acceleration = if player.glide_timer.is_some() {
+            WIND_ACCELERATION
+        } else {
+            WIND_ACCELERATION / 3.
+        };
+        velocity.linvel.y =
+            (velocity.linvel.y + acceleration * time.delta_seconds()).min(WIND_MAX_RISE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generate::{Generator, NoiseSettings},
+        headless_run,
+        player::FLOAT_HEIGHT,
+    };
+
+    /// Walking onto a boost pad sets off the boost.
+    #[test]
+    fn boost_pads_trigger() {
+        let mut generator = Generator::from_u64_seed(
+            0,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        );
+        let column = (0..1024)
+            .find(|x| generator.get_feature(*x) == Some(Feature::BoostPad))
+            .expect("the seed has boost pads");
+        let floor_top = generator.get_height(column) as f32 + 1.;
+        let mut app = headless_run(generator);
+        let player = app
+            .world
+            .query_filtered::<Entity, With<Player>>()
+            .single(&app.world);
+        app.world.get_mut::<Transform>(player).unwrap().translation =
+            Vec3::new(column as f32 * 2., floor_top + FLOAT_HEIGHT, 0.);
+
+        let mut boosted = false;
+        for _ in 0..60 {
+            app.update();
+            boosted |= app.world.get::<Player>(player).unwrap().speed_boost.is_some();
+        }
+        assert!(boosted);
+    }
+}
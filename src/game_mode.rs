@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    discord::ActivityState,
+    events::{DeathCause, RunFinished},
+    hud::{set_text, ModeDisplay},
+    input::Player,
+    level::{KillPlayer, Level},
+    settings::SettingsCycleOption,
+    AppState, InGameState,
+};
+
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>();
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            (
+                spawn_chase_wall,
+                mode_presence.after(crate::level::start_level),
+            ),
+        );
+        app.add_systems(OnExit(AppState::InGame), despawn_chase_wall);
+        app.add_systems(Update, finished_presence);
+        app.add_systems(
+            FixedUpdate,
+            (start_mode, chase_wall, mode_display)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub enum GameMode {
+    /// Travel as far as possible before the run timer runs out.
+    #[default]
+    Timed,
+    /// No timer, the run only ends when the player falls.
+    Endless,
+    /// No timer, falling costs a life and respawns the player on the last checkpoint.
+    Survival,
+    /// No timer, falling always respawns the player on the last checkpoint.
+    Practice,
+    /// No timer, a wall chases the player from the left and speeds up over time.
+    ChaseWall,
+}
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Timed => "Timed",
+            GameMode::Endless => "Endless",
+            GameMode::Survival => "Survival",
+            GameMode::Practice => "Practice",
+            GameMode::ChaseWall => "Chase Wall",
+        }
+    }
+    pub fn is_timed(&self) -> bool {
+        matches!(self, GameMode::Timed)
+    }
+    /// Whether falling respawns the player no matter how many lives are left.
+    pub fn unlimited_respawns(&self) -> bool {
+        matches!(self, GameMode::Practice)
+    }
+    /// Lives at the start of a run, `None` when the first fall ends it.
+    pub fn lives(&self) -> Option<u8> {
+        match self {
+            GameMode::Survival => Some(SURVIVAL_LIVES),
+            _ => None,
+        }
+    }
+}
+impl SettingsCycleOption for GameMode {
+    fn next(&self) -> Self {
+        match self {
+            GameMode::Timed => GameMode::Endless,
+            GameMode::Endless => GameMode::Survival,
+            GameMode::Survival => GameMode::Practice,
+            GameMode::Practice => GameMode::ChaseWall,
+            GameMode::ChaseWall => GameMode::Timed,
+        }
+    }
+}
+
+const SURVIVAL_LIVES: u8 = 3;
+const CHASE_WALL_START: f32 = -40.;
+const CHASE_WALL_BASE_SPEED: f32 = 6.;
+/// Extra speed per second of run time.
+const CHASE_WALL_RAMP: f32 = 0.05;
+const CHASE_WALL_HEIGHT: f32 = 400.;
+
+#[derive(Component)]
+struct ChaseWall {
+    elapsed: f32,
+}
+
+fn spawn_chase_wall(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if *mode != GameMode::ChaseWall {
+        return;
+    }
+    commands.spawn((
+        ChaseWall { elapsed: 0. },
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(2., CHASE_WALL_HEIGHT, 4.)),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgba(1., 0.1, 0.1, 0.5),
+                emissive: Color::RED,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            transform: Transform::from_xyz(CHASE_WALL_START, 0., 0.),
+            ..default()
+        },
+    ));
+}
+
+fn despawn_chase_wall(mut commands: Commands, walls: Query<Entity, With<ChaseWall>>) {
+    for wall in walls.iter() {
+        commands.entity(wall).despawn_recursive();
+    }
+}
+
+fn chase_wall(
+    time: Res<Time>,
+    mut walls: Query<(&mut ChaseWall, &mut Transform), Without<Player>>,
+    player: Query<&Transform, With<Player>>,
+    mut kills: EventWriter<KillPlayer>,
+) {
+    let Ok((mut wall, mut wall_transform)) = walls.get_single_mut() else {
+        return;
+    };
+    let player_transform = player.single();
+    wall.elapsed += time.delta_seconds();
+    let speed = CHASE_WALL_BASE_SPEED + CHASE_WALL_RAMP * wall.elapsed;
+    wall_transform.translation.x += speed * time.delta_seconds();
+    // Keep the wall level with the player so it always fills the screen.
+    wall_transform.translation.y = player_transform.translation.y;
+    if player_transform.translation.x - 0.4 <= wall_transform.translation.x + 1. {
+        kills.send(KillPlayer(DeathCause::ChaseWall));
+    }
+}
+
+fn mode_presence(mode: Res<GameMode>, mut discord_activity: ResMut<ActivityState>) {
+    discord_activity.state = Some(format!("Playing Solo ({})", mode.label()));
+}
+
+fn finished_presence(
+    mode: Res<GameMode>,
+    mut finished: EventReader<RunFinished>,
+    mut discord_activity: ResMut<ActivityState>,
+) {
+    for finished in finished.read() {
+        discord_activity.state = Some(format!(
+            "Finished {} - Score {:.0}",
+            mode.label(),
+            finished.score
+        ));
+    }
+}
+
+fn start_mode(mut level: Query<&mut Level, Added<Level>>, mode: Res<GameMode>) {
+    if let Ok(mut level) = level.get_single_mut() {
+        level.lives = mode.lives();
+    }
+}
+
+fn mode_display(
+    mode: Res<GameMode>,
+    level: Query<&Level>,
+    mut mode_text: Query<&mut Text, With<ModeDisplay>>,
+) {
+    let level = level.single();
+    if let Ok(mut mode_text) = mode_text.get_single_mut() {
+        let text = match level.lives {
+            Some(lives) => format!("Mode: {} - Lives: {}", mode.label(), lives),
+            None if mode.unlimited_respawns() => {
+                format!("Mode: {} - Respawns: {}", mode.label(), level.respawns)
+            }
+            None => format!("Mode: {}", mode.label()),
+        };
+        set_text(&mut mode_text, text);
+    }
+}
@@ -8,14 +8,111 @@ use strum::{EnumCount, IntoEnumIterator};
 
 use crate::upgrades::*;
 
+/// Bumped whenever a change would make an existing seed produce a different level. New levels
+/// use this version, older ones stay reproducible through [`Generator::from_seed_versioned`].
+pub const GENERATOR_VERSION: u8 = 2;
+/// Every version `Generator` can still reproduce, oldest first.
+pub const SUPPORTED_GENERATOR_VERSIONS: [u8; 2] = [1, 2];
+
+#[derive(Debug, PartialEq)]
+pub struct UnsupportedGeneratorVersion(pub u8);
+
 #[derive(Resource, Clone)]
 pub struct Generator {
     seed: [u8; 32],
+    version: u8,
+    height_noise_settings: NoiseSettings,
+    hole_noise_settings: NoiseSettings,
     height_generator: cosiest_noisiest::NoiseGenerator<f64>,
     hole_generator: cosiest_noisiest::NoiseGenerator<f64>,
     upgrades: WeightedUpgrades<ChaCha20Rng, UpgradeType>,
+    hazard_rng: ChaCha20Rng,
+    feature_rng: ChaCha20Rng,
+    pickup_rng: ChaCha20Rng,
+    platform_rng: ChaCha20Rng,
+    /// The checkpoint interval `is_checkpoint` last looked at and its checkpoint column, so the
+    /// interval is only searched once.
+    checkpoint_cache: Option<(usize, Option<usize>)>,
+    /// The chunk `is_gap` last laid out and which of its columns the spawner leaves empty.
+    gap_cache: Option<(usize, Vec<bool>)>,
+}
+
+/// How much less likely each upgrade tier is than the one below it.
+pub const UPGRADE_WEIGHT_FALLOFF: f64 = 0.2;
+
+/// Columns at the start of the track that never get a hazard.
+pub const HAZARD_FREE_COLUMNS: usize = 16;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Hazard {
+    Spikes,
+    /// A gate that knocks the player back for `period` seconds, then is off for `period` seconds.
+    Laser { period: f32, phase: f32 },
+    Crumbling,
+}
+
+/// Columns between checkpoints.
+pub const CHECKPOINT_INTERVAL: usize = 128;
+
+/// World units between two columns, each holds one floor cube.
+pub const COLUMN_WIDTH: f32 = 2.;
+/// The column under world position `x`.
+pub fn column(x: f32) -> usize {
+    (x / COLUMN_WIDTH).round().max(0.) as usize
+}
+/// Columns at the start of the level that are always solid, so the player spawns on the ground.
+pub const SPAWN_COLUMNS: usize = 6;
+/// Columns the spawner generates at once.
+pub const CHUNK_COLUMNS: usize = 1024;
+/// Holes the spawner leaves out of a chunk before it forces the next column back in. Solid
+/// ground in between doesn't reset the count, see `Generator::is_gap`.
+pub const MAX_HOLE_STREAK: usize = 5;
+/// Rise between two columns that a base jump can't clear, so it gets a spring.
+pub const SPRING_RISE: f64 = 4.;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Feature {
+    BoostPad,
+    /// Launches the player high enough to clear a rise of `height`.
+    Spring { height: f32 },
+    /// An updraft over the `width` holes following the column.
+    WindZone { width: usize },
+}
+
+/// Height of a platform's resting position above the floor cube of its column, so its underside
+/// stays clear of a player walking below and its top can be landed on with a base jump.
+pub const PLATFORM_HEIGHT: f32 = 5.;
+/// Half the thickness of a platform slab.
+pub const PLATFORM_HALF_THICKNESS: f32 = 0.25;
+
+/// A floating platform, offset from the column it belongs to in column widths and cube heights.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Platform {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub motion: Option<PlatformMotion>,
+}
+
+/// A sine path around the platform's resting position.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlatformMotion {
+    pub vertical: bool,
+    pub amplitude: f32,
+    pub period: f32,
+    pub phase: f32,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Pickup {
+    TimeShard,
+    ScoreOrb,
+    UpgradeCrate,
+}
+
+/// More octaves than this only add detail far below a column's width.
+pub const MAX_NOISE_OCTAVES: usize = 16;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct NoiseSettings {
     wave_length: usize,
     amplitude: f64,
@@ -29,6 +126,22 @@ impl NoiseSettings {
             octaves,
         }
     }
+    pub fn wave_length(&self) -> usize {
+        self.wave_length
+    }
+    pub fn amplitude(&self) -> f64 {
+        self.amplitude
+    }
+    pub fn octaves(&self) -> usize {
+        self.octaves
+    }
+    /// Whether a generator can be built from these settings, for settings that come from
+    /// outside the game such as share codes.
+    pub fn is_valid(&self) -> bool {
+        self.wave_length > 0
+            && self.amplitude.is_finite()
+            && (1..=MAX_NOISE_OCTAVES).contains(&self.octaves)
+    }
 }
 
 impl Generator {
@@ -58,22 +171,69 @@ impl Generator {
         let rng = ChaCha20Rng::from_seed(seed);
         Self::new(rng, height_noise_settings, hole_noise_settings)
     }
+    /// Recreates a level made by an older generator, e.g. from a share code or a saved score.
+    pub fn from_seed_versioned(
+        seed: [u8; 32],
+        version: u8,
+        height_noise_settings: NoiseSettings,
+        hole_noise_settings: NoiseSettings,
+    ) -> Result<Self, UnsupportedGeneratorVersion> {
+        let rng = ChaCha20Rng::from_seed(seed);
+        Self::new_versioned(rng, version, height_noise_settings, hole_noise_settings)
+    }
     pub fn get_seed(&self) -> [u8; 32] {
         self.seed
     }
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+    pub fn height_noise_settings(&self) -> NoiseSettings {
+        self.height_noise_settings
+    }
+    pub fn hole_noise_settings(&self) -> NoiseSettings {
+        self.hole_noise_settings
+    }
     pub fn new(
         rng: ChaCha20Rng,
         height_noise_settings: NoiseSettings,
         hole_noise_settings: NoiseSettings,
     ) -> Self {
+        Self::new_versioned(
+            rng,
+            GENERATOR_VERSION,
+            height_noise_settings,
+            hole_noise_settings,
+        )
+        .expect("current generator version is supported")
+    }
+    pub fn new_versioned(
+        rng: ChaCha20Rng,
+        version: u8,
+        height_noise_settings: NoiseSettings,
+        hole_noise_settings: NoiseSettings,
+    ) -> Result<Self, UnsupportedGeneratorVersion> {
+        if !SUPPORTED_GENERATOR_VERSIONS.contains(&version) {
+            return Err(UnsupportedGeneratorVersion(version));
+        }
         let mut height_rng = rng.clone();
         height_rng.set_stream(1);
         let mut upgrade_rng = rng.clone();
         upgrade_rng.set_stream(2);
         let mut hole_rng = rng.clone();
         hole_rng.set_stream(3);
+        let mut hazard_rng = rng.clone();
+        hazard_rng.set_stream(4);
+        let mut feature_rng = rng.clone();
+        feature_rng.set_stream(5);
+        let mut pickup_rng = rng.clone();
+        pickup_rng.set_stream(6);
+        let mut platform_rng = rng.clone();
+        platform_rng.set_stream(7);
         let mut result = Self {
             seed: rng.get_seed(),
+            version,
+            height_noise_settings,
+            hole_noise_settings,
             height_generator: NoiseGenerator::from_rng(
                 height_rng,
                 Frequency::from_wave_length(height_noise_settings.wave_length),
@@ -87,15 +247,42 @@ impl Generator {
                 hole_noise_settings.octaves,
             ),
             upgrades: WeightedUpgrades::new(upgrade_rng),
+            hazard_rng,
+            feature_rng,
+            pickup_rng,
+            platform_rng,
+            checkpoint_cache: None,
+            gap_cache: None,
         };
+        result.add_upgrades(UPGRADE_WEIGHT_FALLOFF);
+        Ok(result)
+    }
 
-        let weight_offset = 0.2;
+    /// Rebuilds the upgrade pool from scratch with a different tier weight falloff, for tuning.
+    pub fn with_upgrade_weight_falloff(mut self, weight_falloff: f64) -> Self {
+        let mut upgrade_rng = ChaCha20Rng::from_seed(self.seed);
+        upgrade_rng.set_stream(2);
+        self.upgrades = WeightedUpgrades::new(upgrade_rng);
+        self.add_upgrades(weight_falloff);
+        self
+    }
+
+    fn add_upgrades(&mut self, weight_falloff: f64) {
+        match self.version {
+            1 => self.add_upgrades_v1(weight_falloff),
+            2 => self.add_upgrades_v2(weight_falloff),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+
+    /// The upgrade pool of version 1. Later versions add to it, so it must never change.
+    fn add_upgrades_v1(&mut self, weight_falloff: f64) {
         let mut weight = 50_000_000.;
         for (i, upgrade_level) in crate::upgrades::UpgradeLevel::iter().enumerate() {
             if upgrade_level == crate::upgrades::UpgradeLevel::None {
                 continue;
             }
-            result.upgrades.add_upgrade(
+            self.upgrades.add_upgrade(
                 UpgradeType::Speed(StatUpgrade {
                     modifier: 1. + (0.1 * (i as f32)),
                     additive: false,
@@ -103,7 +290,7 @@ impl Generator {
                 }),
                 weight,
             );
-            result.upgrades.add_upgrade(
+            self.upgrades.add_upgrade(
                 UpgradeType::JumpPower(StatUpgrade {
                     modifier: 0.5 * (i as f32),
                     additive: true,
@@ -113,7 +300,7 @@ impl Generator {
             );
             if upgrade_level != UpgradeLevel::Basic {
                 if i % (UpgradeLevel::COUNT / 4) == 0 {
-                    result.upgrades.add_upgrade(
+                    self.upgrades.add_upgrade(
                         UpgradeType::GlideSkill(crate::upgrades::GlideSkill {
                             max_uses: 1 + (i / 4) as u8,
                             tier: upgrade_level,
@@ -124,7 +311,7 @@ impl Generator {
                     );
                 }
                 if i % (UpgradeLevel::COUNT / 3) == 0 {
-                    result.upgrades.add_upgrade(
+                    self.upgrades.add_upgrade(
                         UpgradeType::JumpSkill(crate::upgrades::JumpSkill {
                             max_jumps: 1 + (i / 3) as u8,
                             tier: upgrade_level,
@@ -134,7 +321,7 @@ impl Generator {
                     );
                 }
                 if i % 2 == 0 {
-                    result.upgrades.add_upgrade(
+                    self.upgrades.add_upgrade(
                         UpgradeType::DashSkill(crate::upgrades::DashSkill {
                             max_dash: 1 + (i / 4) as u8,
                             tier: upgrade_level,
@@ -145,9 +332,32 @@ impl Generator {
                     );
                 }
             }
-            weight *= weight_offset;
+            weight *= weight_falloff;
+        }
+    }
+    /// Version 1 plus the time slow skill, from Advanced to Mythic.
+    fn add_upgrades_v2(&mut self, weight_falloff: f64) {
+        self.add_upgrades_v1(weight_falloff);
+        let mut weight = 50_000_000.;
+        for (i, upgrade_level) in crate::upgrades::UpgradeLevel::iter().enumerate() {
+            if upgrade_level == crate::upgrades::UpgradeLevel::None {
+                continue;
+            }
+            if upgrade_level >= UpgradeLevel::Advanced && i % 2 == 0 {
+                self.upgrades.add_upgrade(
+                    UpgradeType::TimeSlowSkill(crate::upgrades::TimeSlowSkill {
+                        max_uses: 1 + (i / 5) as u8,
+                        tier: upgrade_level,
+                        cooldown: Duration::from_secs(20),
+                        max_duration: Duration::from_secs_f64(i as f64 / 2.),
+                        time_scale: 1. - (0.08 * (i as f32)),
+                        slows_timer: upgrade_level < UpgradeLevel::Master,
+                    }),
+                    weight,
+                );
+            }
+            weight *= weight_falloff;
         }
-        result
     }
     pub fn get_height(&mut self, x: usize) -> f64 {
         self.height_generator.sample(x)
@@ -165,4 +375,362 @@ impl Generator {
     pub fn is_hole(&mut self, x: usize) -> bool {
         self.hole_generator.sample(x) >= self.hole_generator.amplitude * 0.95
     }
+
+    /// Whether the spawner leaves column `x` empty. The hole streak starts over with every chunk
+    /// and only the column after `MAX_HOLE_STREAK` holes resets it, so the chunk is laid out once.
+    pub fn is_gap(&mut self, x: usize) -> bool {
+        if x < SPAWN_COLUMNS {
+            return false;
+        }
+        let chunk = x / CHUNK_COLUMNS;
+        let start = chunk * CHUNK_COLUMNS;
+        if let Some((cached, gaps)) = &self.gap_cache {
+            if *cached == chunk {
+                return gaps[x - start];
+            }
+        }
+        let mut hole_streak = 0;
+        let gaps: Vec<bool> = (start..start + CHUNK_COLUMNS)
+            .map(|column| {
+                if column < SPAWN_COLUMNS {
+                    false
+                } else if hole_streak >= MAX_HOLE_STREAK {
+                    hole_streak = 0;
+                    false
+                } else if self.is_hole(column) {
+                    hole_streak += 1;
+                    true
+                } else {
+                    false
+                }
+            })
+            .collect();
+        let gap = gaps[x - start];
+        self.gap_cache = Some((chunk, gaps));
+        gap
+    }
+
+    // Placement goes through the version like the upgrade pool does. Version 1 levels have
+    // nothing but floor, a new version gets its own copies of the rules it changes.
+    pub fn get_hazard(&self, x: usize) -> Option<Hazard> {
+        match self.version {
+            1 => None,
+            2 => self.get_hazard_v2(x),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+    pub fn is_checkpoint(&mut self, x: usize) -> bool {
+        match self.version {
+            1 => false,
+            2 => self.is_checkpoint_v2(x),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+    pub fn get_pickup(&self, x: usize) -> Option<Pickup> {
+        match self.version {
+            1 => None,
+            2 => self.get_pickup_v2(x),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+    pub fn get_platform(&mut self, x: usize) -> Option<Platform> {
+        match self.version {
+            1 => None,
+            2 => self.get_platform_v2(x),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+    pub fn get_feature(&mut self, x: usize) -> Option<Feature> {
+        match self.version {
+            1 => None,
+            2 => self.get_feature_v2(x),
+            _ => unreachable!("checked against SUPPORTED_GENERATOR_VERSIONS"),
+        }
+    }
+
+    /// Hazards are rolled per column from a fixed position in the hazard stream, so placement
+    /// doesn't depend on the order chunks are generated in.
+    fn get_hazard_v2(&self, x: usize) -> Option<Hazard> {
+        if x < HAZARD_FREE_COLUMNS {
+            return None;
+        }
+        let mut rng = self.hazard_rng.clone();
+        rng.set_word_pos(x as u128 * 16);
+        let roll: f64 = rng.gen();
+        if roll < 0.02 {
+            Some(Hazard::Spikes)
+        } else if roll < 0.03 {
+            Some(Hazard::Laser {
+                period: rng.gen_range(1.0..2.5),
+                phase: rng.gen_range(0.0..2.5),
+            })
+        } else if roll < 0.05 {
+            Some(Hazard::Crumbling)
+        } else {
+            None
+        }
+    }
+
+    /// Checkpoints sit on the first safe column of every `CHECKPOINT_INTERVAL` columns.
+    fn is_checkpoint_v2(&mut self, x: usize) -> bool {
+        if x < CHECKPOINT_INTERVAL {
+            return false;
+        }
+        let interval = x / CHECKPOINT_INTERVAL;
+        let checkpoint = match self.checkpoint_cache {
+            Some((cached, checkpoint)) if cached == interval => checkpoint,
+            _ => {
+                let start = interval * CHECKPOINT_INTERVAL;
+                let checkpoint = (start..start + CHECKPOINT_INTERVAL)
+                    .find(|column| !self.is_hole(*column) && self.get_hazard(*column).is_none());
+                self.checkpoint_cache = Some((interval, checkpoint));
+                checkpoint
+            }
+        };
+        checkpoint == Some(x)
+    }
+
+    /// Pickups float above solid columns that have no hazard, rolled the same way as hazards.
+    fn get_pickup_v2(&self, x: usize) -> Option<Pickup> {
+        if x < HAZARD_FREE_COLUMNS || self.get_hazard(x).is_some() {
+            return None;
+        }
+        let mut rng = self.pickup_rng.clone();
+        rng.set_word_pos(x as u128 * 16);
+        let roll: f64 = rng.gen();
+        if roll < 0.002 {
+            Some(Pickup::UpgradeCrate)
+        } else if roll < 0.012 {
+            Some(Pickup::TimeShard)
+        } else if roll < 0.06 {
+            Some(Pickup::ScoreOrb)
+        } else {
+            None
+        }
+    }
+
+    /// Platforms form a second tier over the holes following a column and above high cliff tops.
+    fn get_platform_v2(&mut self, x: usize) -> Option<Platform> {
+        if x < HAZARD_FREE_COLUMNS || self.is_hole(x) {
+            return None;
+        }
+        let hole_ahead = self.is_hole(x + 1);
+        let cliff = self.get_height(x) - self.get_height(x - 1) > SPRING_RISE;
+        if !hole_ahead && !cliff {
+            return None;
+        }
+        let mut rng = self.platform_rng.clone();
+        rng.set_word_pos(x as u128 * 16);
+        if rng.gen_bool(0.5) {
+            return None;
+        }
+        let motion = if rng.gen_bool(0.4) {
+            Some(PlatformMotion {
+                vertical: rng.gen_bool(0.5),
+                amplitude: rng.gen_range(1.5..4.0),
+                period: rng.gen_range(2.0..5.0),
+                phase: rng.gen_range(0.0..std::f32::consts::TAU),
+            })
+        } else {
+            None
+        };
+        // Vertical motion only takes the platform up from its resting position.
+        let lift = motion
+            .filter(|motion| motion.vertical)
+            .map_or(0., |motion| motion.amplitude);
+        Some(if hole_ahead {
+            Platform {
+                offset_x: 1.5,
+                offset_y: PLATFORM_HEIGHT + lift,
+                motion,
+            }
+        } else {
+            Platform {
+                offset_x: 0.,
+                offset_y: PLATFORM_HEIGHT + 2. + lift,
+                motion,
+            }
+        })
+    }
+
+    /// Features are placed where they keep the track reachable: updrafts over holes and springs
+    /// in front of cliffs, with boost pads scattered over flat ground.
+    fn get_feature_v2(&mut self, x: usize) -> Option<Feature> {
+        if x < HAZARD_FREE_COLUMNS || self.get_hazard(x).is_some() || self.is_hole(x) {
+            return None;
+        }
+        let mut width = 0;
+        while width < MAX_HOLE_STREAK && self.is_hole(x + 1 + width) {
+            width += 1;
+        }
+        if width > 0 {
+            return Some(Feature::WindZone { width });
+        }
+        let rise = self.get_height(x + 1) - self.get_height(x);
+        if rise > SPRING_RISE {
+            return Some(Feature::Spring {
+                height: rise as f32,
+            });
+        }
+        let mut rng = self.feature_rng.clone();
+        rng.set_word_pos(x as u128 * 16);
+        if rise.abs() < 1. && rng.gen::<f64>() < 0.02 {
+            Some(Feature::BoostPad)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn generator(seed: u64) -> Generator {
+        Generator::from_u64_seed(
+            seed,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        )
+    }
+    /// Heights, holes, placement and the first upgrades of a seed, in a diffable text form.
+    fn golden_output(generator: &mut Generator) -> String {
+        let mut output = String::new();
+        for x in 0..256 {
+            output += &format!("{:?}\n", generator.get_height(x));
+        }
+        output += &(0..1024)
+            .map(|x| if generator.is_hole(x) { '#' } else { '.' })
+            .collect::<String>();
+        output += "\n";
+        for x in 0..1024 {
+            let placed = (
+                generator.get_hazard(x),
+                generator.get_feature(x),
+                generator.get_pickup(x),
+                generator.get_platform(x),
+                generator.is_checkpoint(x),
+            );
+            if placed != (None, None, None, None, false) {
+                output += &format!("{} {:?}\n", x, placed);
+            }
+        }
+        for _ in 0..32 {
+            output += &format!("{:?}\n", generator.get_upgrade());
+        }
+        output
+    }
+    /// Pins what every supported version produces for a few seeds. Run with `UPDATE_GOLDEN=1` to
+    /// record the files of a new version, a missing file fails like a mismatch does.
+    #[test]
+    fn golden_seeds() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        for version in SUPPORTED_GENERATOR_VERSIONS {
+            for seed in [&b"golden"[..], b"sky_scapade", b"0123456789abcdef"] {
+                let mut padded = [0_u8; 32];
+                padded[..seed.len()].copy_from_slice(seed);
+                let mut generator = Generator::from_seed_versioned(
+                    padded,
+                    version,
+                    NoiseSettings::new(256_usize, 64, 5),
+                    NoiseSettings::new(9_usize, 64, 3),
+                )
+                .unwrap();
+                let output = golden_output(&mut generator);
+                let path = dir.join(format!(
+                    "generator-v{}-{}.txt",
+                    version,
+                    std::str::from_utf8(seed).unwrap()
+                ));
+                if update {
+                    std::fs::create_dir_all(&dir).unwrap();
+                    std::fs::write(&path, output).unwrap();
+                    continue;
+                }
+                let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+                    panic!(
+                        "{} is missing, record it with UPDATE_GOLDEN=1",
+                        path.display()
+                    )
+                });
+                assert!(
+                    expected == output,
+                    "{} no longer matches, bump GENERATOR_VERSION instead",
+                    path.display()
+                );
+            }
+        }
+    }
+    #[test]
+    fn unknown_versions_are_rejected() {
+        let settings = || NoiseSettings::new(9_usize, 64, 3);
+        assert!(Generator::from_seed_versioned([0; 32], 0, settings(), settings()).is_err());
+        assert_eq!(generator(0).version(), GENERATOR_VERSION);
+    }
+    #[test]
+    fn version_one_is_plain_floor() {
+        let settings = || NoiseSettings::new(9_usize, 64, 3);
+        let mut generator =
+            Generator::from_seed_versioned([1; 32], 1, settings(), settings()).unwrap();
+        for x in 0..4096 {
+            assert_eq!(generator.get_hazard(x), None);
+            assert_eq!(generator.get_feature(x), None);
+            assert_eq!(generator.get_pickup(x), None);
+            assert_eq!(generator.get_platform(x), None);
+            assert!(!generator.is_checkpoint(x));
+        }
+    }
+    #[test]
+    fn hazards_are_deterministic() {
+        let first = generator(0);
+        let mut second = generator(0);
+        // Drawing upgrades must not shift where hazards land.
+        second.get_upgrade();
+        for x in (0..4096).rev() {
+            assert_eq!(first.get_hazard(x), second.get_hazard(x));
+        }
+        assert!((0..HAZARD_FREE_COLUMNS).all(|x| first.get_hazard(x).is_none()));
+        assert!((0..4096).any(|x| first.get_hazard(x).is_some()));
+    }
+    #[test]
+    fn platforms_follow_holes_and_cliffs() {
+        let mut generator = generator(3);
+        let platforms: Vec<usize> = (0..4096)
+            .filter(|x| generator.get_platform(*x).is_some())
+            .collect();
+        assert!(!platforms.is_empty());
+        for x in platforms {
+            assert!(x >= HAZARD_FREE_COLUMNS && !generator.is_hole(x));
+            let cliff = generator.get_height(x) - generator.get_height(x - 1) > SPRING_RISE;
+            assert!(generator.is_hole(x + 1) || cliff);
+        }
+    }
+    #[test]
+    fn platforms_clear_the_walking_player() {
+        // Floor top, float height and the capsule's half height and radius.
+        let walking_top = 1. + 2. + 1.4;
+        let mut generator = generator(3);
+        for x in 0..4096 {
+            let Some(platform) = generator.get_platform(x) else {
+                continue;
+            };
+            let drop = platform
+                .motion
+                .filter(|motion| motion.vertical)
+                .map_or(0., |motion| motion.amplitude);
+            assert!(platform.offset_y - drop - PLATFORM_HALF_THICKNESS > walking_top);
+        }
+    }
+    #[test]
+    fn one_safe_checkpoint_per_interval() {
+        let mut generator = generator(2);
+        let checkpoints: Vec<usize> = (0..CHECKPOINT_INTERVAL * 8)
+            .filter(|x| generator.is_checkpoint(*x))
+            .collect();
+        assert_eq!(checkpoints.len(), 7);
+        for x in checkpoints {
+            assert!(!generator.is_hole(x));
+            assert!(generator.get_hazard(x).is_none());
+        }
+    }
 }
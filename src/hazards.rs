@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    events::DeathCause, generate::Hazard, input::Player, level::KillPlayer, player::FLOAT_GAP,
+    AppState, InGameState,
+};
+
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HazardAssets>();
+        app.add_systems(
+            Update,
+            (hazard_collisions, laser_gates, crumbling_platforms)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+const LASER_KNOCKBACK: f32 = 25.;
+const LASER_HEIGHT: f32 = 12.;
+/// Sensors on a floor cube reach this far above it, into the capsule of a player standing there.
+pub const FLOOR_SENSOR_HEIGHT: f32 = FLOAT_GAP + 0.4;
+
+#[derive(Resource, Clone)]
+pub struct HazardAssets {
+    spikes_mesh: Handle<Mesh>,
+    spikes_material: Handle<StandardMaterial>,
+    laser_mesh: Handle<Mesh>,
+    laser_material: Handle<StandardMaterial>,
+    crumbling_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for HazardAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let spikes_mesh = meshes.add(Cuboid::new(1.8, 0.4, 1.8));
+        let laser_mesh = meshes.add(Cuboid::new(0.2, LASER_HEIGHT, 0.2));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            spikes_mesh,
+            spikes_material: materials.add(StandardMaterial {
+                base_color: Color::DARK_GRAY,
+                metallic: 0.8,
+                ..default()
+            }),
+            laser_mesh,
+            laser_material: materials.add(StandardMaterial {
+                base_color: Color::RED,
+                emissive: Color::RED,
+                unlit: true,
+                ..default()
+            }),
+            crumbling_material: materials.add(StandardMaterial {
+                base_color: Color::rgb_u8(139, 90, 43),
+                ..default()
+            }),
+        }
+    }
+}
+
+#[derive(Component)]
+struct HazardSensor(Hazard);
+
+#[derive(Component)]
+struct LaserGate {
+    timer: Timer,
+    active: bool,
+}
+
+#[derive(Component)]
+struct Crumbling {
+    floor: Entity,
+    timer: Option<Timer>,
+}
+
+/// Spawns the hazard for a column on top of the already spawned `floor` cube at `x`, `y`.
+pub fn spawn_hazard(
+    commands: &mut Commands,
+    assets: &HazardAssets,
+    level: Entity,
+    floor: Entity,
+    hazard: Hazard,
+    x: f32,
+    y: f32,
+) {
+    match hazard {
+        Hazard::Spikes => {
+            commands
+                .spawn(PbrBundle {
+                    mesh: assets.spikes_mesh.clone(),
+                    material: assets.spikes_material.clone(),
+                    transform: Transform::from_xyz(x, y + 1.2, 0.),
+                    ..default()
+                })
+                .set_parent(level);
+            commands.entity(floor).with_children(|floor_cube| {
+                floor_cube.spawn((HazardSensor(hazard), floor_sensor()));
+            });
+        }
+        Hazard::Laser { period, phase } => {
+            let mut timer = Timer::from_seconds(period, TimerMode::Repeating);
+            timer.set_elapsed(Duration::from_secs_f32(phase % period));
+            let active = ((phase / period) as u32) % 2 == 0;
+            let mut laser = commands.spawn((
+                HazardSensor(hazard),
+                LaserGate { timer, active },
+                Collider::cuboid(0.1, LASER_HEIGHT / 2., 1.),
+                Sensor,
+                ActiveEvents::COLLISION_EVENTS,
+                PbrBundle {
+                    mesh: assets.laser_mesh.clone(),
+                    material: assets.laser_material.clone(),
+                    transform: Transform::from_xyz(x, y + 1. + LASER_HEIGHT / 2., 0.),
+                    visibility: if active {
+                        Visibility::Inherited
+                    } else {
+                        Visibility::Hidden
+                    },
+                    ..default()
+                },
+            ));
+            if !active {
+                laser.insert(ColliderDisabled);
+            }
+            laser.set_parent(level);
+        }
+        Hazard::Crumbling => {
+            commands
+                .entity(floor)
+                .insert(assets.crumbling_material.clone())
+                .with_children(|floor_cube| {
+                    floor_cube.spawn((
+                        HazardSensor(hazard),
+                        Crumbling { floor, timer: None },
+                        floor_sensor(),
+                    ));
+                });
+        }
+    }
+}
+
+/// A sensor covering the top of the floor cube it is spawned under.
+fn floor_sensor() -> impl Bundle {
+    (
+        Collider::cuboid(0.9, FLOOR_SENSOR_HEIGHT / 2., 0.9),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        TransformBundle::from_transform(Transform::from_xyz(0., 1. + FLOOR_SENSOR_HEIGHT / 2., 0.)),
+    )
+}
+
+fn hazard_collisions(
+    mut collision_events: EventReader<CollisionEvent>,
+    sensors: Query<&HazardSensor>,
+    mut crumbling: Query<&mut Crumbling>,
+    mut player: Query<(Entity, &mut Velocity), With<Player>>,
+    mut kills: EventWriter<KillPlayer>,
+) {
+    let Ok((player_entity, mut velocity)) = player.get_single_mut() else {
+        return;
+    };
+    for event in collision_events.read() {
+        let CollisionEvent::Started(a, b, _) = event else {
+            continue;
+        };
+        let other = if *a == player_entity {
+            *b
+        } else if *b == player_entity {
+            *a
+        } else {
+            continue;
+        };
+        let Ok(sensor) = sensors.get(other) else {
+            continue;
+        };
+        match sensor.0 {
+            Hazard::Spikes => {
+                kills.send(KillPlayer(DeathCause::Spikes));
+            }
+            Hazard::Laser { .. } => {
+                velocity.linvel = Vec3::new(-LASER_KNOCKBACK, LASER_KNOCKBACK / 2., 0.);
+            }
+            Hazard::Crumbling => {
+                if let Ok(mut crumbling) = crumbling.get_mut(other) {
+                    if crumbling.timer.is_none() {
+                        crumbling.timer = Some(Timer::from_seconds(0.6, TimerMode::Once));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn laser_gates(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lasers: Query<(Entity, &mut LaserGate, &mut Visibility)>,
+) {
+    for (entity, mut laser, mut visibility) in lasers.iter_mut() {
+        laser.timer.tick(time.delta());
+        if laser.timer.times_finished_this_tick() % 2 == 1 {
+            laser.active = !laser.active;
+            if laser.active {
+                *visibility = Visibility::Inherited;
+                commands.entity(entity).remove::<ColliderDisabled>();
+            } else {
+                *visibility = Visibility::Hidden;
+                commands.entity(entity).insert(ColliderDisabled);
+            }
+        }
+    }
+}
+
+fn crumbling_platforms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut platforms: Query<&mut Crumbling>,
+) {
+    for mut crumbling in platforms.iter_mut() {
+        if let Some(ref mut timer) = crumbling.timer {
+            timer.tick(time.delta());
+            if timer.just_finished() {
+                commands.entity(crumbling.floor).despawn_recursive();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::ManualEventReader;
+
+    use super::*;
+    use crate::{
+        events::PlayerDied,
+        generate::{Generator, NoiseSettings},
+        headless_run,
+        player::FLOAT_HEIGHT,
+    };
+
+    /// A player standing still on spikes is killed by them, not left floating above the sensor.
+    #[test]
+    fn standing_on_spikes_kills() {
+        let mut generator = Generator::from_u64_seed(
+            0,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        );
+        let column = (0..1024)
+            .find(|x| generator.get_hazard(*x) == Some(Hazard::Spikes) && !generator.is_hole(*x))
+            .expect("the seed has spikes");
+        let floor_top = generator.get_height(column) as f32 + 1.;
+        let mut app = headless_run(generator);
+        let player = app
+            .world
+            .query_filtered::<Entity, With<Player>>()
+            .single(&app.world);
+        app.world.get_mut::<Transform>(player).unwrap().translation =
+            Vec3::new(column as f32 * 2., floor_top + FLOAT_HEIGHT, 0.);
+
+        let mut deaths = ManualEventReader::<PlayerDied>::default();
+        let mut causes = Vec::new();
+        for _ in 0..120 {
+            app.update();
+            causes.extend(
+                deaths
+                    .read(app.world.resource::<Events<PlayerDied>>())
+                    .map(|died| died.cause),
+            );
+        }
+        assert_eq!(causes, [DeathCause::Spikes]);
+        assert_eq!(
+            app.world.resource::<State<InGameState>>().get(),
+            &InGameState::End
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    game_mode, generate, input::Player, level::Level, scores, share_code, AppState, InGameState,
+};
+
+pub mod progress;
+mod skill_slots;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((progress::ProgressPlugin, skill_slots::SkillSlotsPlugin));
+        app.add_systems(OnEnter(AppState::InGame), spawn_hud);
+        app.add_systems(
+            FixedUpdate,
+            (update_score, update_time)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(OnExit(InGameState::End), leave_end_screen);
+    }
+}
+
+#[derive(Component)]
+pub struct Score;
+#[derive(Component)]
+pub struct TimeDisplay;
+#[derive(Component)]
+pub struct ModeDisplay;
+#[derive(Component)]
+pub struct EndScreen;
+
+/// Replaces the text of a HUD element, leaving it untouched if nothing changed so it isn't
+/// marked as changed and laid out again.
+pub fn set_text(text: &mut Mut<Text>, value: String) {
+    if text.sections[0].value != value {
+        text.sections[0].value = value;
+    }
+}
+
+fn update_score(player: Query<&Player>, mut score: Query<&mut Text, With<Score>>) {
+    let player = player.single();
+    if let Ok(mut score_text) = score.get_single_mut() {
+        set_text(&mut score_text, format!("Score: {:.0}", player.score));
+    }
+}
+
+fn update_time(
+    level: Query<&Level>,
+    mode: Res<game_mode::GameMode>,
+    mut time_text: Query<&mut Text, With<TimeDisplay>>,
+) {
+    let level = level.single();
+    if let Ok(mut time_text) = time_text.get_single_mut() {
+        let time = if mode.is_timed() {
+            level.timer.elapsed()
+        } else {
+            level.elapsed
+        }
+        .as_secs();
+        set_text(
+            &mut time_text,
+            format!("Time: {:02}:{:02}", time / 60, time % 60),
+        );
+    }
+}
+
+pub fn spawn_end_screen(commands: &mut Commands, score: f32) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    display: Display::Grid,
+                    align_items: AlignItems::Center,
+                    justify_items: JustifyItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..default()
+            },
+            EndScreen,
+        ))
+        .with_children(|screen| {
+            screen.spawn(
+                TextBundle::from_section(
+                    format!("Final Score\n{:.0}", score),
+                    TextStyle {
+                        font_size: 72.,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+        });
+}
+
+fn leave_end_screen(mut commands: Commands, screen: Query<Entity, With<EndScreen>>) {
+    if let Ok(screen) = screen.get_single() {
+        commands.entity(screen).despawn_recursive();
+    }
+}
+
+/// Run info on the left, progress in the middle and the skill slots on the right of the safe
+/// area.
+fn spawn_hud(
+    mut commands: Commands,
+    safe_ui: Query<Entity, With<crate::SafeUi>>,
+    generator: Res<generate::Generator>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let seed = scores::seed_label(&generator.get_seed());
+    let safe_ui = safe_ui.get_single();
+    if let Ok(safe_ui) = safe_ui {
+        let mut safe_ui = commands.entity(safe_ui);
+        safe_ui.with_children(|ui| {
+            ui.spawn(NodeBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(1.)),
+                    width: Val::Percent(100.),
+                    display: Display::Grid,
+                    grid_template_columns: vec![
+                        GridTrack::auto(),
+                        GridTrack::fr(1.0),
+                        GridTrack::auto(),
+                    ],
+                    ..default()
+                },
+                //border_color:Color::RED.into(),
+                ..default()
+            })
+            .with_children(|ui| {
+                ui.spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        grid_column: GridPlacement::start(1),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|ui| {
+                    ui.spawn(TextBundle::from_section(
+                        format!("Seed: {}", seed),
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ));
+                    ui.spawn(TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 16.0,
+                            ..default()
+                        },
+                    ))
+                    .insert(share_code::ShareCodeDisplay);
+
+                    ui.spawn(TextBundle::from_section(
+                        format!("Score: {}", 0.0),
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ))
+                    .insert(Score);
+                    ui.spawn(TextBundle::from_section(
+                        format!("Time: {:?}", Duration::from_secs(0)),
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ))
+                    .insert(TimeDisplay);
+                    ui.spawn(TextBundle::from_section(
+                        "Mode:".to_string(),
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ))
+                    .insert(ModeDisplay);
+                });
+                ui.spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        grid_column: GridPlacement::start(2),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(progress::spawn_progress);
+                ui.spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        grid_column: GridPlacement::start(3),
+                        justify_content: JustifyContent::Center,
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|skills| {
+                    skill_slots::spawn_skill_slots(skills, &mut images);
+                });
+            });
+        });
+    }
+}
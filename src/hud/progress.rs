@@ -0,0 +1,268 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+use super::set_text;
+use crate::{
+    distance_posts::DISTANCE_POST_SPACING, input::Player, level::Level, stats::RunStats, AppState,
+    InGameState,
+};
+
+pub struct ProgressPlugin;
+
+impl Plugin for ProgressPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PersonalBest>();
+        app.add_systems(
+            FixedUpdate,
+            (speed_gauge, distance_bar)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+/// World units are feet, so this turns units per second into miles per hour.
+pub const MPH_PER_UNIT: f32 = 0.681818;
+const GAUGE_WIDTH: f32 = 160.;
+const GAUGE_SLOW_COLOR: Color = Color::rgb(0.3, 0.8, 1.);
+const GAUGE_FAST_COLOR: Color = Color::ORANGE_RED;
+
+/// Best earlier run on the same seed, generator version and mode, raced as a ghost.
+#[derive(Resource, Default, Debug)]
+pub struct PersonalBest {
+    pub distance: f32,
+    /// `(seconds, distance)` at the end of every minute of the run and at its end.
+    pub splits: Vec<(f32, f32)>,
+}
+impl PersonalBest {
+    pub fn from_run(run: &RunStats) -> Self {
+        let mut splits = Vec::with_capacity(run.distance_per_minute.len());
+        let mut distance = 0.;
+        for (minute, covered) in run.distance_per_minute.iter().enumerate() {
+            distance += covered;
+            let time = (60. * (minute + 1) as f32).min(run.duration);
+            splits.push((time, distance));
+        }
+        Self {
+            distance: run.distance,
+            splits,
+        }
+    }
+    /// Where the personal best run was `elapsed` seconds in, assuming an even pace within each
+    /// minute. Stays at the finish once the run is over.
+    pub fn ghost_distance(&self, elapsed: f32) -> Option<f32> {
+        let mut previous = (0., 0.);
+        for &(time, distance) in &self.splits {
+            if elapsed <= time {
+                let fraction = if time > previous.0 {
+                    (elapsed - previous.0) / (time - previous.0)
+                } else {
+                    1.
+                };
+                return Some(previous.1 + (distance - previous.1) * fraction);
+            }
+            previous = (time, distance);
+        }
+        self.splits.last().map(|split| split.1)
+    }
+}
+
+#[derive(Component)]
+struct SpeedFill;
+#[derive(Component)]
+struct SpeedText;
+#[derive(Component)]
+struct DistanceFill;
+#[derive(Component)]
+struct DistanceText;
+#[derive(Component, Clone, Copy, PartialEq)]
+enum DistanceMarker {
+    PersonalBest,
+    Ghost,
+}
+
+/// Distance bar with its markers over a speed gauge, centered in the HUD column `ui`.
+pub fn spawn_progress(ui: &mut ChildBuilder) {
+    let text_style = TextStyle {
+        color: Color::WHITE,
+        font_size: 20.0,
+        ..default()
+    };
+    ui.spawn(TextBundle::from_section("", text_style.clone()))
+        .insert(DistanceText);
+    ui.spawn(NodeBundle {
+        style: Style {
+            width: Val::Percent(60.),
+            height: Val::Px(10.),
+            margin: UiRect::vertical(Val::Px(4.)),
+            ..default()
+        },
+        background_color: Color::rgba(1., 1., 1., 0.15).into(),
+        ..default()
+    })
+    .with_children(|bar| {
+        bar.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(0.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                ..default()
+            },
+            DistanceFill,
+        ));
+        for (marker, color) in [
+            (DistanceMarker::PersonalBest, Color::GOLD),
+            (DistanceMarker::Ghost, Color::rgba(0.6, 0.9, 1., 0.8)),
+        ] {
+            bar.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        display: Display::None,
+                        width: Val::Px(3.),
+                        height: Val::Px(18.),
+                        top: Val::Px(-4.),
+                        ..default()
+                    },
+                    background_color: color.into(),
+                    ..default()
+                },
+                marker,
+            ));
+        }
+    });
+    ui.spawn(NodeBundle {
+        style: Style {
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.),
+            ..default()
+        },
+        ..default()
+    })
+    .with_children(|row| {
+        row.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(GAUGE_WIDTH),
+                height: Val::Px(14.),
+                border: UiRect::all(Val::Px(2.)),
+                ..default()
+            },
+            border_color: Color::WHITE.into(),
+            background_color: Color::rgba(0., 0., 0., 0.6).into(),
+            ..default()
+        })
+        .with_children(|gauge| {
+            gauge.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: GAUGE_SLOW_COLOR.into(),
+                    ..default()
+                },
+                SpeedFill,
+            ));
+        });
+        row.spawn(TextBundle::from_section("", text_style))
+            .insert(SpeedText);
+    });
+}
+
+/// Fills up towards the player's current top speed and shifts color as it does.
+fn speed_gauge(
+    mut player: Query<(&Velocity, &mut Player)>,
+    mut fill: Query<(&mut Style, &mut BackgroundColor), With<SpeedFill>>,
+    mut text: Query<&mut Text, With<SpeedText>>,
+) {
+    let (velocity, mut player) = player.single_mut();
+    let speed = velocity.linvel.x.abs();
+    let fraction = (speed / player.max_speed()).clamp(0., 1.);
+    if let Ok((mut style, mut color)) = fill.get_single_mut() {
+        let width = Val::Percent(fraction * 100.);
+        if style.width != width {
+            style.width = width;
+        }
+        let target = GAUGE_SLOW_COLOR
+            .rgb_linear_to_vec3()
+            .lerp(GAUGE_FAST_COLOR.rgb_linear_to_vec3(), fraction);
+        let target = Color::rgb_linear(target.x, target.y, target.z);
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+    if let Ok(mut text) = text.get_single_mut() {
+        set_text(&mut text, format!("{:.0} mph", speed * MPH_PER_UNIT));
+    }
+}
+
+type DistanceBarQueries<'w, 's> = (
+    Query<'w, 's, &'static mut Style, (With<DistanceFill>, Without<DistanceMarker>)>,
+    Query<'w, 's, (&'static DistanceMarker, &'static mut Style), Without<DistanceFill>>,
+    Query<'w, 's, &'static mut Text, With<DistanceText>>,
+);
+fn distance_bar(
+    player: Query<&Player>,
+    level: Query<&Level>,
+    best: Res<PersonalBest>,
+    bar: DistanceBarQueries,
+) {
+    let (mut fill, mut markers, mut text) = bar;
+    let player = player.single();
+    let elapsed = level.single().elapsed.as_secs_f32();
+    let scale = best
+        .distance
+        .max(player.distance)
+        .max(DISTANCE_POST_SPACING);
+    if let Ok(mut style) = fill.get_single_mut() {
+        let width = Val::Percent(player.distance / scale * 100.);
+        if style.width != width {
+            style.width = width;
+        }
+    }
+    for (marker, mut style) in markers.iter_mut() {
+        let distance = match marker {
+            DistanceMarker::PersonalBest => Some(best.distance).filter(|distance| *distance > 0.),
+            DistanceMarker::Ghost => best.ghost_distance(elapsed),
+        };
+        let (display, left) = match distance {
+            Some(distance) => (Display::Flex, Val::Percent(distance / scale * 100.)),
+            None => (Display::None, Val::Auto),
+        };
+        if style.display != display || style.left != left {
+            style.display = display;
+            style.left = left;
+        }
+    }
+    if let Ok(mut text) = text.get_single_mut() {
+        let value = if best.distance > 0. {
+            format!("{:.0} / Best {:.0}", player.distance, best.distance)
+        } else {
+            format!("{:.0}", player.distance)
+        };
+        set_text(&mut text, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn ghost_follows_splits() {
+        let best = PersonalBest::from_run(&RunStats {
+            duration: 90.,
+            distance: 900.,
+            distance_per_minute: vec![600., 300.],
+            ..default()
+        });
+        assert_eq!(best.ghost_distance(0.), Some(0.));
+        assert_eq!(best.ghost_distance(30.), Some(300.));
+        assert_eq!(best.ghost_distance(75.), Some(750.));
+        assert_eq!(best.ghost_distance(600.), Some(900.));
+        assert_eq!(PersonalBest::default().ghost_distance(10.), None);
+    }
+}
@@ -0,0 +1,315 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use bevy_tnua::controller::TnuaController;
+
+use super::set_text;
+use crate::{input::Player, player::spawn_player, upgrades::UpgradeLevel, AppState, InGameState};
+
+pub struct SkillSlotsPlugin;
+
+impl Plugin for SkillSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            (slot_borders, slot_charges)
+                .after(super::spawn_hud)
+                .after(spawn_player),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (slot_borders, slot_charges, slot_bars)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+const ICON_SIZE: f32 = 40.;
+const BAR_HEIGHT: f32 = 4.;
+const COOLDOWN_COLOR: Color = Color::rgb(0.55, 0.75, 1.);
+const ACTIVE_COLOR: Color = Color::GOLD;
+
+/// A skill shown in the HUD, bordered with the color of its upgrade tier.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub enum SkillSlot {
+    Jump,
+    Dash,
+    Glide,
+    TimeSlow,
+}
+impl SkillSlot {
+    const ALL: [SkillSlot; 4] = [
+        SkillSlot::Dash,
+        SkillSlot::Jump,
+        SkillSlot::Glide,
+        SkillSlot::TimeSlow,
+    ];
+    fn tier(&self, player: &Player) -> UpgradeLevel {
+        match self {
+            SkillSlot::Jump => player.jump_skill.tier,
+            SkillSlot::Dash => player.dash_skill.tier,
+            SkillSlot::Glide => player.glide_skill.tier,
+            SkillSlot::TimeSlow => player.time_slow_skill.tier,
+        }
+    }
+    fn charges(&self, player: &Player, airborne: bool) -> String {
+        match self {
+            SkillSlot::Jump => {
+                // The jump off the ground is the first, `jump_index` counts the air jumps after it.
+                let used = match (airborne, player.jump_skill.air) {
+                    (false, _) => 0,
+                    (true, true) => 1 + player.jump_index as u8,
+                    (true, false) => player.jump_skill.max_jumps,
+                };
+                player.jump_skill.max_jumps.saturating_sub(used).to_string()
+            }
+            SkillSlot::Dash => {
+                let air = if player.dash_skill.air { " Air" } else { "" };
+                format!("{}{}", player.dash_skill.max_dash - player.used_dashes, air)
+            }
+            SkillSlot::Glide => (player.glide_skill.max_uses - player.used_glides).to_string(),
+            SkillSlot::TimeSlow => {
+                (player.time_slow_skill.max_uses - player.used_time_slows).to_string()
+            }
+        }
+    }
+    /// Timer until the next charge comes back.
+    fn cooldown<'a>(&self, player: &'a Player) -> Option<&'a Timer> {
+        match self {
+            SkillSlot::Jump => None,
+            SkillSlot::Dash => player.dash_cooldown.as_ref(),
+            SkillSlot::Glide => player.glide_cooldown.as_ref(),
+            SkillSlot::TimeSlow => player.time_slow_cooldown.as_ref(),
+        }
+    }
+    /// Timer of the skill while it is in use.
+    fn active<'a>(&self, player: &'a Player) -> Option<&'a Timer> {
+        match self {
+            SkillSlot::Jump | SkillSlot::Dash => None,
+            SkillSlot::Glide => player.glide_timer.as_ref(),
+            SkillSlot::TimeSlow => player.time_slow_timer.as_ref(),
+        }
+    }
+    fn has_bar(&self, kind: SkillBarKind) -> bool {
+        match kind {
+            SkillBarKind::Cooldown => *self != SkillSlot::Jump,
+            SkillBarKind::Active => matches!(self, SkillSlot::Glide | SkillSlot::TimeSlow),
+        }
+    }
+    /// 8x8 pixel art, `#` pixels are drawn white.
+    #[rustfmt::skip]
+    fn icon(&self) -> [&'static str; 8] {
+        match self {
+            SkillSlot::Jump => [
+                "...##...",
+                "..####..",
+                ".##..##.",
+                "##....##",
+                "...##...",
+                "..####..",
+                ".##..##.",
+                "##....##",
+            ],
+            SkillSlot::Dash => [
+                "........",
+                "#...##..",
+                "##...##.",
+                "###...##",
+                "###...##",
+                "##...##.",
+                "#...##..",
+                "........",
+            ],
+            SkillSlot::Glide => [
+                "........",
+                "...##...",
+                "..####..",
+                ".######.",
+                "########",
+                "#..##..#",
+                "...##...",
+                "..####..",
+            ],
+            SkillSlot::TimeSlow => [
+                "########",
+                ".#....#.",
+                "..#..#..",
+                "...##...",
+                "...##...",
+                "..#..#..",
+                ".######.",
+                "########",
+            ],
+        }
+    }
+    fn icon_image(&self) -> Image {
+        let mut data = Vec::with_capacity(8 * 8 * 4);
+        for row in self.icon() {
+            for pixel in row.bytes() {
+                let alpha = if pixel == b'#' { 255 } else { 0 };
+                data.extend_from_slice(&[255, 255, 255, alpha]);
+            }
+        }
+        Image::new(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+    }
+}
+
+#[derive(Component)]
+struct SkillCharges(SkillSlot);
+
+#[derive(Clone, Copy, PartialEq)]
+enum SkillBarKind {
+    Cooldown,
+    Active,
+}
+/// Fill of a bar under a slot, `Cooldown` grows back to full as a charge recovers and `Active`
+/// drains while the skill lasts.
+#[derive(Component)]
+struct SkillBar {
+    slot: SkillSlot,
+    kind: SkillBarKind,
+}
+
+/// Spawns one slot per skill into the HUD column `ui`.
+pub fn spawn_skill_slots(ui: &mut ChildBuilder, images: &mut Assets<Image>) {
+    for slot in SkillSlot::ALL {
+        let icon = images.add(slot.icon_image());
+        ui.spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    grid_template_columns: vec![GridTrack::px(ICON_SIZE), GridTrack::px(56.)],
+                    column_gap: Val::Px(6.),
+                    align_items: AlignItems::Center,
+                    margin: UiRect::bottom(Val::Px(6.)),
+                    padding: UiRect::all(Val::Px(4.)),
+                    border: UiRect::all(Val::Px(3.)),
+                    ..default()
+                },
+                border_color: UpgradeLevel::None.color().into(),
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..default()
+            },
+            slot,
+        ))
+        .with_children(|slot_ui| {
+            slot_ui.spawn(ImageBundle {
+                style: Style {
+                    width: Val::Px(ICON_SIZE),
+                    height: Val::Px(ICON_SIZE),
+                    ..default()
+                },
+                image: UiImage::new(icon),
+                ..default()
+            });
+            slot_ui
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(3.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|column| {
+                    column.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                color: Color::WHITE,
+                                font_size: 24.0,
+                                ..default()
+                            },
+                        ),
+                        SkillCharges(slot),
+                    ));
+                    for (kind, color) in [
+                        (SkillBarKind::Cooldown, COOLDOWN_COLOR),
+                        (SkillBarKind::Active, ACTIVE_COLOR),
+                    ] {
+                        if !slot.has_bar(kind) {
+                            continue;
+                        }
+                        column
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Percent(100.),
+                                    height: Val::Px(BAR_HEIGHT),
+                                    ..default()
+                                },
+                                background_color: Color::rgba(1., 1., 1., 0.15).into(),
+                                ..default()
+                            })
+                            .with_children(|bar| {
+                                bar.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            width: Val::Percent(0.),
+                                            height: Val::Percent(100.),
+                                            ..default()
+                                        },
+                                        background_color: color.into(),
+                                        ..default()
+                                    },
+                                    SkillBar { slot, kind },
+                                ));
+                            });
+                    }
+                });
+        });
+    }
+}
+
+fn slot_borders(player: Query<&Player>, mut slots: Query<(&SkillSlot, &mut BorderColor)>) {
+    let player = player.single();
+    for (slot, mut border) in slots.iter_mut() {
+        let color = slot.tier(player).color();
+        if border.0 != color {
+            border.0 = color;
+        }
+    }
+}
+
+fn slot_charges(
+    player: Query<(&Player, &TnuaController)>,
+    mut charges: Query<(&SkillCharges, &mut Text)>,
+) {
+    let (player, controller) = player.single();
+    let airborne = matches!(controller.is_airborne(), Ok(true));
+    for (SkillCharges(slot), mut text) in charges.iter_mut() {
+        set_text(&mut text, slot.charges(player, airborne));
+    }
+}
+
+fn slot_bars(player: Query<&Player>, mut bars: Query<(&SkillBar, &mut Style)>) {
+    let player = player.single();
+    for (bar, mut style) in bars.iter_mut() {
+        let fill = match bar.kind {
+            SkillBarKind::Cooldown => bar.slot.cooldown(player).map_or(0., Timer::fraction),
+            SkillBarKind::Active => bar
+                .slot
+                .active(player)
+                .map_or(0., Timer::fraction_remaining),
+        };
+        let width = Val::Percent(fill * 100.);
+        if style.width != width {
+            style.width = width;
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
-use crate::{DashSkill, JumpSkill, StatUpgrade,GlideSkill};
+use crate::{DashSkill, JumpSkill, StatUpgrade,GlideSkill,TimeSlowSkill};
 
 #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
 pub enum Action {
@@ -11,8 +11,13 @@ pub enum Action {
     Jump,
     Dash,
     Glide,
+    TimeSlow,
     Accept,
 }
+/// `Player::base_speed` of a new player.
+pub const BASE_SPEED:f32 = 10.;
+/// `Player::base_jump_power` of a new player.
+pub const BASE_JUMP_POWER:f32 = 5.;
 #[derive(Component,Default)]
 pub struct Player{
     pub base_speed:f32,
@@ -20,20 +25,34 @@ pub struct Player{
     pub base_jump_power:f32,
     pub jump_modifiers:Vec<StatUpgrade>,
     pub score:f32,
+    pub distance:f32,
+    pub bonus_score:f32,
     pub jump_skill:JumpSkill,
+    pub jump_index:usize,
     pub dash_skill:DashSkill,
     pub used_dashes:u8,
     pub dash_cooldown:Option<Timer>,
     pub glide_skill:GlideSkill,
     pub used_glides:u8,
     pub glide_cooldown:Option<Timer>,
-    pub glide_timer:Option<Timer>
+    pub glide_timer:Option<Timer>,
+    pub time_slow_skill:TimeSlowSkill,
+    pub used_time_slows:u8,
+    pub time_slow_cooldown:Option<Timer>,
+    pub time_slow_timer:Option<Timer>,
+    pub speed_boost:Option<SpeedBoost>
     }
+/// A speed modifier that runs out, kept apart from the upgrades in `speed_modifiers`.
+pub struct SpeedBoost{
+    pub upgrade:StatUpgrade,
+    pub timer:Timer,
+}
 impl Player{
     pub fn max_speed(&mut self)->f32{
         let mut result = self.base_speed;
         self.speed_modifiers.sort_unstable_by(|a,b| a.additive.cmp(&b.additive));
-        for modifier in &self.speed_modifiers  {
+        let boost = self.speed_boost.as_ref().map(|boost| &boost.upgrade);
+        for modifier in self.speed_modifiers.iter().chain(boost)  {
             if modifier.additive{
                 result += modifier.modifier;
             }else{
@@ -0,0 +1,454 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bevy::{
+    log,
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    checkpoints, discord, events, features, game_mode, generate, hazards, hud, input::Player,
+    pickups, platforms, player::Glider, scores, AppState, InGameState,
+};
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<KillPlayer>();
+        app.add_systems(OnEnter(AppState::InGame), start_level);
+        app.add_systems(OnExit(AppState::InGame), cleanup_level);
+        app.add_systems(
+            FixedUpdate,
+            (generate_more_if_needed, level_finish, killing_floor)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(
+            Update,
+            player_deaths
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(OnEnter(InGameState::Paused), pause_level);
+        app.add_systems(OnExit(InGameState::Paused), resume_level);
+        app.add_systems(OnEnter(InGameState::Upgrade), pause_level);
+        app.add_systems(OnExit(InGameState::Upgrade), resume_level);
+        app.add_systems(OnEnter(InGameState::End), pause_level);
+        app.add_systems(OnExit(InGameState::End), resume_level);
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct PlatformAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Root of everything spawned for a run, despawned when leaving the game.
+#[derive(Component)]
+pub struct Level {
+    pub right: usize,
+    pub upgrade_timer: Timer,
+    pub timer: Timer,
+    /// Collected upgrade crates still waiting for their roll.
+    pub upgrade_crates: usize,
+    /// Run time, which keeps counting in modes without a timer.
+    pub elapsed: Duration,
+    /// Remaining lives in modes that respawn the player after a fall.
+    pub lives: Option<u8>,
+    /// Respawn point of the furthest checkpoint reached so far.
+    pub checkpoint: Vec3,
+    pub respawns: u32,
+}
+
+#[derive(Component)]
+pub struct LevelFloor;
+
+/// Sent by anything that kills the player. Handled in one place, so every cause goes through the
+/// same lives check and a run ends only once even if the player dies twice in a frame.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct KillPlayer(pub events::DeathCause);
+
+fn pause_level(mut physics: ResMut<RapierConfiguration>) {
+    physics.physics_pipeline_active = false;
+}
+fn resume_level(mut physics: ResMut<RapierConfiguration>) {
+    physics.physics_pipeline_active = true;
+}
+
+fn level_finish(
+    mut commands: Commands,
+    mut level: Query<&mut Level>,
+    time: Res<Time>,
+    virtual_time: Res<Time<Virtual>>,
+    player: Query<(&Transform, &Player)>,
+    mode: Res<game_mode::GameMode>,
+    mut next_state: ResMut<NextState<InGameState>>,
+) {
+    let mut level = level.single_mut();
+    let (player, player_stats) = player.single();
+    let mut delta = time.delta();
+    if player_stats.time_slow_timer.is_some() && !player_stats.time_slow_skill.slows_timer {
+        // Higher tier time slow leaves the run clock ticking in real time.
+        delta = delta.div_f32(virtual_time.relative_speed());
+    }
+    level.timer.tick(delta);
+    level.elapsed += delta;
+
+    if mode.is_timed() && level.timer.just_finished() {
+        log::info!("Level Finished. Travelled: {}", player.translation.x);
+        hud::spawn_end_screen(&mut commands, player_stats.score);
+        next_state.set(InGameState::End);
+    }
+}
+
+type LevelContentAssets<'a> = (
+    Res<'a, hazards::HazardAssets>,
+    Res<'a, features::FeatureAssets>,
+    Res<'a, pickups::PickupAssets>,
+    Res<'a, platforms::FloatingPlatformAssets>,
+    Res<'a, checkpoints::CheckpointAssets>,
+);
+fn generate_more_if_needed(
+    mut commands: Commands,
+    mut level: Query<(Entity, &mut Level)>,
+    platform_assets: Res<PlatformAssets>,
+    content_assets: LevelContentAssets,
+    player: Query<&Transform, With<Player>>,
+    mut generator: ResMut<generate::Generator>,
+    mut chunks: EventWriter<events::ChunkGenerated>,
+) {
+    let (level_entity, mut level) = level.single_mut();
+    let cube_size = 1.0f32;
+    let player_transform = player.single();
+    if (player_transform.translation.x / cube_size) >= (level.right * 2) as f32 - 100. {
+        let generate_offset = level.right;
+        let heights = generator.get_heights(generate_offset);
+        for (x, height) in heights.into_iter().enumerate() {
+            place_column(
+                &mut commands,
+                &mut generator,
+                level_entity,
+                &platform_assets,
+                &content_assets,
+                x + generate_offset,
+                height,
+            );
+        }
+        level.right += heights.len();
+        info!("level.right: {}", level.right);
+        chunks.send(events::ChunkGenerated {
+            start: generate_offset,
+            end: level.right,
+        });
+    }
+}
+
+fn cleanup_level(mut commands: Commands, level: Query<Entity, With<Level>>) {
+    let level = level.iter();
+    for level in level {
+        commands.entity(level).despawn_recursive();
+    }
+}
+
+fn killing_floor(
+    player: Query<&Transform, With<Player>>,
+    mut generator: ResMut<generate::Generator>,
+    mut kills: EventWriter<KillPlayer>,
+) {
+    let player_transform = player.single();
+    let y = generator.get_height((player_transform.translation.x / 2.) as usize) as f32;
+    if player_transform.translation.y < y - 10. {
+        kills.send(KillPlayer(events::DeathCause::Fall));
+    }
+}
+
+/// Respawns the player on the last checkpoint while the mode allows it, ends the run otherwise.
+fn player_deaths(
+    mut commands: Commands,
+    mut kills: EventReader<KillPlayer>,
+    mut player: Query<(&mut Transform, &mut Velocity, &mut Player)>,
+    gliders: Query<Entity, With<Glider>>,
+    mut level: Query<&mut Level>,
+    mode: Res<game_mode::GameMode>,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut deaths: EventWriter<events::PlayerDied>,
+) {
+    let Some(KillPlayer(cause)) = kills.read().last().copied() else {
+        return;
+    };
+    let (mut player_transform, mut velocity, mut player) = player.single_mut();
+    let mut level = level.single_mut();
+    if let Some(lives) = &mut level.lives {
+        *lives = lives.saturating_sub(1);
+    }
+    let respawned = mode.unlimited_respawns() || level.lives.is_some_and(|lives| lives > 0);
+    deaths.send(events::PlayerDied {
+        cause,
+        position: player_transform.translation,
+        respawned,
+    });
+    if respawned {
+        checkpoints::respawn_player(
+            &mut commands,
+            &gliders,
+            &mut level,
+            &mut player,
+            &mut player_transform,
+            &mut velocity,
+        );
+    } else {
+        hud::spawn_end_screen(&mut commands, player.score);
+        next_state.set(InGameState::End);
+    }
+}
+
+type StartLevelAssets<'a> = (
+    Res<'a, AssetServer>,
+    ResMut<'a, Assets<Image>>,
+    ResMut<'a, Assets<StandardMaterial>>,
+    LevelContentAssets<'a>,
+);
+/// Spawns the [`Level`] with its first chunk of floor, the player is added by
+/// [`crate::player::spawn_player`] once it exists.
+pub fn start_level(
+    mut commands: Commands,
+    assets: StartLevelAssets,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut discord_activity: ResMut<discord::ActivityState>,
+    mut generator: ResMut<generate::Generator>,
+) {
+    next_state.set(InGameState::Playing);
+
+    let (asset_server, mut images, mut materials, content_assets) = assets;
+    let platform_mesh: Handle<Mesh> = asset_server.load("platform.obj");
+    let debug_material = materials.add(StandardMaterial {
+        base_color_texture: Some(images.add(uv_debug_texture())),
+        ..default()
+    });
+    let seed = scores::seed_label(&generator.get_seed());
+    discord_activity.details = Some(format!("Seed: {}", seed));
+    discord_activity.start = Some(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .try_into()
+            .unwrap(),
+    );
+    let platform_assets = PlatformAssets {
+        mesh: platform_mesh.clone(),
+        material: debug_material.clone(),
+    };
+    commands.insert_resource(platform_assets.clone());
+    let heights = generator.get_heights(0);
+    let cube_size = 1.0f32;
+    let spawn_point = Vec3::new(1.5 * cube_size, (heights[0] as f32) + (3.5 * cube_size), 0.);
+    let level = commands
+        .spawn((
+            Level {
+                right: heights.len(),
+                upgrade_timer: Timer::new(Duration::from_secs(10), TimerMode::Repeating),
+                timer: Timer::new(Duration::from_secs(300), TimerMode::Once),
+                upgrade_crates: 0,
+                elapsed: Duration::ZERO,
+                lives: None,
+                checkpoint: spawn_point,
+                respawns: 0,
+            },
+            TransformBundle::default(),
+            VisibilityBundle::default(),
+        ))
+        .id();
+    info!("level.right: {}", heights.len());
+    let first_chunk = events::ChunkGenerated {
+        start: 0,
+        end: heights.len(),
+    };
+    commands.add(move |world: &mut World| {
+        world.send_event(first_chunk);
+    });
+
+    commands
+        .spawn(Collider::cuboid(cube_size, cube_size, cube_size))
+        .insert(PbrBundle {
+            mesh: platform_mesh.clone(),
+            material: debug_material.clone(),
+            ..default()
+        })
+        .insert(LevelFloor)
+        .insert(TransformBundle::from_transform(Transform::from_xyz(
+            0.,
+            (heights[0] as f32) * cube_size,
+            0.,
+        )))
+        .set_parent(level);
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            illuminance: light_consts::lux::AMBIENT_DAYLIGHT,
+            color: Color::rgb_u8(234, 212, 165),
+            ..default()
+        },
+        transform: Transform::from_xyz(10.0, 60.0, -10.0).looking_at(Vec3::splat(0.0), Vec3::Y),
+        ..default()
+    });
+
+    for (x, hy) in heights.into_iter().enumerate().skip(1).take(5) {
+        let hy = (hy as f32) * cube_size;
+        commands
+            .spawn(Collider::cuboid(cube_size, cube_size, cube_size))
+            .insert(PbrBundle {
+                mesh: platform_mesh.clone(),
+                material: debug_material.clone(),
+                ..default()
+            })
+            .insert(LevelFloor)
+            .insert(TransformBundle::from_transform(Transform::from_xyz(
+                (x as f32) * cube_size * 2.,
+                hy,
+                0.,
+            )))
+            .set_parent(level);
+    }
+    for (x, height) in heights
+        .into_iter()
+        .enumerate()
+        .skip(generate::SPAWN_COLUMNS)
+    {
+        place_column(
+            &mut commands,
+            &mut generator,
+            level,
+            &platform_assets,
+            &content_assets,
+            x,
+            height,
+        );
+    }
+}
+
+/// Spawns the floor cube of column `x` with the hazard, feature, pickup, platform and checkpoint
+/// the generator puts on it, or nothing over a gap.
+fn place_column(
+    commands: &mut Commands,
+    generator: &mut generate::Generator,
+    level: Entity,
+    platform_assets: &PlatformAssets,
+    content_assets: &LevelContentAssets,
+    x: usize,
+    height: f64,
+) {
+    let (hazard_assets, feature_assets, pickup_assets, floating_platform_assets, checkpoint_assets) =
+        content_assets;
+    if generator.is_gap(x) {
+        return;
+    }
+    let hazard = generator.get_hazard(x);
+    let feature = generator.get_feature(x);
+    let pickup = generator.get_pickup(x);
+    let platform = generator.get_platform(x);
+    let checkpoint = generator.is_checkpoint(x);
+    let cube_size = 1.0f32;
+    let x = (x as f32) * generate::COLUMN_WIDTH;
+    let y = (height as f32) * cube_size;
+    let floor = commands
+        .spawn(Collider::cuboid(cube_size, cube_size, cube_size))
+        .insert(PbrBundle {
+            mesh: platform_assets.mesh.clone(),
+            material: platform_assets.material.clone(),
+            ..default()
+        })
+        .insert(LevelFloor)
+        .insert(TransformBundle::from_transform(Transform::from_xyz(
+            x, y, 0.,
+        )))
+        .set_parent(level)
+        .id();
+    if let Some(hazard) = hazard {
+        hazards::spawn_hazard(commands, hazard_assets, level, floor, hazard, x, y);
+    }
+    if let Some(feature) = feature {
+        features::spawn_feature(commands, feature_assets, level, feature, x, y);
+    }
+    if let Some(pickup) = pickup {
+        pickups::spawn_pickup(commands, pickup_assets, level, pickup, x, y);
+    }
+    if let Some(platform) = platform {
+        platforms::spawn_platform(commands, floating_platform_assets, level, platform, x, y);
+    }
+    if checkpoint {
+        checkpoints::spawn_checkpoint(commands, checkpoint_assets, level, x, y);
+    }
+}
+
+fn uv_debug_texture() -> Image {
+    const TEXTURE_SIZE: usize = 8;
+
+    let mut palette: [u8; 32] = [
+        255, 102, 159, 255, 255, 159, 102, 255, 236, 255, 102, 255, 121, 255, 102, 255, 102, 255,
+        198, 255, 102, 198, 255, 255, 121, 102, 255, 255, 236, 102, 255, 255,
+    ];
+
+    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
+    for y in 0..TEXTURE_SIZE {
+        let offset = TEXTURE_SIZE * y * 4;
+        texture_data[offset..(offset + TEXTURE_SIZE * 4)].copy_from_slice(&palette);
+        palette.rotate_right(4);
+    }
+
+    Image::new_fill(
+        Extent3d {
+            width: TEXTURE_SIZE as u32,
+            height: TEXTURE_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &texture_data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game_mode::GameMode,
+        generate::{Generator, NoiseSettings},
+        headless_run,
+    };
+
+    /// Every life is one death, the last one ends the run.
+    #[test]
+    fn survival_lives() {
+        let mut app = headless_run(Generator::from_u64_seed(
+            0,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        ));
+        *app.world.resource_mut::<GameMode>() = GameMode::Survival;
+        let lives = GameMode::Survival.lives().unwrap();
+        app.world
+            .query::<&mut Level>()
+            .single_mut(&mut app.world)
+            .lives = Some(lives);
+        for death in 1..=lives {
+            app.world.send_event(KillPlayer(events::DeathCause::Spikes));
+            app.update();
+            app.update();
+            let level = app.world.query::<&Level>().single(&app.world);
+            assert_eq!(level.lives, Some(lives - death));
+            let state = app.world.resource::<State<InGameState>>().get();
+            if death < lives {
+                assert_eq!(level.respawns, u32::from(death));
+                assert_eq!(state, &InGameState::Playing);
+            } else {
+                assert_eq!(state, &InGameState::End);
+            }
+        }
+    }
+}
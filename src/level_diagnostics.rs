@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore};
+use bevy_rapier3d::prelude::Collider;
+
+use crate::{
+    debug_overlay::DebugOverlay, diagnostics_recorder::DiagnosticsRecording,
+    events::ChunkGenerated, level::Level, AppState,
+};
+
+/// Counts of what the generator has put into the current level.
+#[derive(Default)]
+pub struct LevelDiagnosticsPlugin;
+impl Plugin for LevelDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_system).add_systems(
+            Update,
+            (collider_count.run_if(colliders_shown), chunk_count)
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+impl LevelDiagnosticsPlugin {
+    /// Colliders anywhere under the `Level` entity.
+    pub const COLLIDERS: DiagnosticPath = DiagnosticPath::const_new("level/colliders");
+    /// Chunks generated since the level was started.
+    pub const CHUNKS: DiagnosticPath = DiagnosticPath::const_new("level/chunks");
+}
+
+fn setup_system(mut diagnostics: ResMut<DiagnosticsStore>) {
+    diagnostics.add(Diagnostic::new(LevelDiagnosticsPlugin::COLLIDERS));
+    diagnostics.add(Diagnostic::new(LevelDiagnosticsPlugin::CHUNKS));
+}
+
+/// Counting walks the whole level, so it only happens while the overlay or a recording uses it.
+fn colliders_shown(
+    overlay: Option<Res<DebugOverlay>>,
+    recording: Option<Res<DiagnosticsRecording>>,
+) -> bool {
+    recording.is_some() || overlay.is_some_and(|overlay| overlay.visible)
+}
+
+fn collider_count(
+    mut diagnostics: Diagnostics,
+    level: Query<Entity, With<Level>>,
+    children: Query<&Children>,
+    colliders: Query<(), With<Collider>>,
+) {
+    let Ok(level) = level.get_single() else {
+        return;
+    };
+    diagnostics.add_measurement(&LevelDiagnosticsPlugin::COLLIDERS, || {
+        colliders
+            .iter_many(children.iter_descendants(level))
+            .count() as f64
+    });
+}
+
+/// Keeps counting until a new `Level` entity shows up.
+fn chunk_count(
+    mut diagnostics: Diagnostics,
+    mut chunks: EventReader<ChunkGenerated>,
+    level: Query<Entity, With<Level>>,
+    mut count: Local<(Option<Entity>, usize)>,
+) {
+    let Ok(level) = level.get_single() else {
+        return;
+    };
+    if count.0 != Some(level) {
+        *count = (Some(level), 0);
+    }
+    count.1 += chunks.read().count();
+    let chunks = count.1;
+    diagnostics.add_measurement(&LevelDiagnosticsPlugin::CHUNKS, || chunks as f64);
+}
@@ -0,0 +1,211 @@
+use bevy::{audio::Volume, ecs::system::SystemParam, log, prelude::*};
+use leafwing_input_manager::action_state::ActionState;
+
+use crate::{
+    events, generate, input, input::Player, interpolate, level::Level, upgrades::*, AppState,
+    InGameState,
+};
+
+/// Rolls an upgrade every time the level's upgrade timer fires or a crate is opened, applies it
+/// to the player and announces it with a slide-in notification.
+pub struct UpgradePlugin;
+
+impl Plugin for UpgradePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            level_upgrade
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        // Achievements unlocked when a run ends still need to slide in on the end screen.
+        app.add_systems(
+            Update,
+            upgrade_notification_timers.run_if(
+                in_state(AppState::InGame)
+                    .and_then(in_state(InGameState::Playing).or_else(in_state(InGameState::End))),
+            ),
+        );
+        app.add_systems(
+            Update,
+            accept_upgrade.run_if(in_state(InGameState::Upgrade)),
+        );
+    }
+}
+
+fn accept_upgrade(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<InGameState>>,
+    action: Query<&ActionState<input::Action>>,
+    screen: Query<Entity, With<UpgradeScreen>>,
+) {
+    let action_state = action.single();
+    if action_state.just_pressed(&input::Action::Accept) {
+        commands.entity(screen.single()).despawn_recursive();
+        next_state.set(InGameState::Playing);
+    }
+}
+
+/// Notification that slides up from the bottom of the screen, stays for a while and slides back.
+#[derive(Component)]
+pub struct UpgradeScreen {
+    opening_timer: Timer,
+    visible_timer: Timer,
+    closing_timer: Timer,
+}
+impl UpgradeScreen {
+    pub fn new() -> Self {
+        Self {
+            opening_timer: Timer::from_seconds(1., TimerMode::Once),
+            visible_timer: Timer::from_seconds(5., TimerMode::Once),
+            closing_timer: Timer::from_seconds(1., TimerMode::Once),
+        }
+    }
+}
+fn upgrade_notification_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut notifications: Query<(Entity, &mut UpgradeScreen, &mut Style)>,
+) {
+    for (notification_entity, mut notification, mut transform) in notifications.iter_mut() {
+        if !notification.opening_timer.finished() {
+            notification.opening_timer.tick(time.delta());
+            transform.bottom = Val::Px(interpolate(
+                -100.,
+                0.,
+                notification.opening_timer.fraction(),
+            ));
+        } else if !notification.visible_timer.finished() {
+            notification.visible_timer.tick(time.delta());
+        } else if !notification.closing_timer.finished() {
+            notification.closing_timer.tick(time.delta());
+            transform.bottom = Val::Px(interpolate(
+                0.,
+                -100.,
+                notification.closing_timer.fraction(),
+            ));
+        } else {
+            let notification_entity = commands.entity(notification_entity);
+            notification_entity.despawn_recursive();
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct UpgradeNotificationContext<'w, 's> {
+    safe_ui: Query<'w, 's, Entity, With<crate::SafeUi>>,
+    asset_server: Res<'w, AssetServer>,
+}
+fn level_upgrade(
+    mut commands: Commands,
+    time: Res<Time>,
+    notification: UpgradeNotificationContext,
+    mut level: Query<&mut Level>,
+    mut player: Query<&mut Player>,
+    mut generator: ResMut<generate::Generator>,
+    mut upgrade_events: EventWriter<events::UpgradeAcquired>,
+) {
+    let UpgradeNotificationContext {
+        safe_ui,
+        asset_server,
+    } = notification;
+    let mut level = level.single_mut();
+    level.upgrade_timer.tick(time.delta());
+    let crate_opened = level.upgrade_crates > 0;
+    if crate_opened {
+        level.upgrade_crates -= 1;
+    }
+    if level.upgrade_timer.just_finished() || crate_opened {
+        let upgrade = generator.get_upgrade();
+        log::info!("Upgrade:{:?}", upgrade);
+        if let Some(upgrade) = upgrade {
+            let mut player = player.single_mut();
+            upgrade_events.send(events::UpgradeAcquired(upgrade));
+            let display;
+            match upgrade {
+                UpgradeType::Speed(upgrade) => {
+                    player.speed_modifiers.push(upgrade);
+                    display = format!("{} ({:?})", "Speed Upgrade", upgrade.tier);
+                }
+                UpgradeType::JumpPower(upgrade) => {
+                    player.jump_modifiers.push(upgrade);
+                    display = format!("{} ({:?})", "Jump Power Upgrade", upgrade.tier);
+                }
+                UpgradeType::JumpSkill(skill) => {
+                    player.jump_skill = skill;
+                    display = format!("{} ({:?})", "Extra Jump Upgrade", skill.tier);
+                }
+                UpgradeType::DashSkill(skill) => {
+                    player.dash_skill = skill;
+                    display = format!("{} ({:?})", "Dash Upgrade", skill.tier);
+                }
+                UpgradeType::GlideSkill(skill) => {
+                    player.glide_skill = skill;
+                    display = format!("{} ({:?})", "Glide Upgrade", skill.tier);
+                }
+                UpgradeType::TimeSlowSkill(skill) => {
+                    player.time_slow_skill = skill;
+                    display = format!("{} ({:?})", "Time Slow Upgrade", skill.tier);
+                }
+            }
+            commands.spawn(AudioBundle {
+                source: asset_server.load("upgrade.mp3"),
+                settings: PlaybackSettings {
+                    mode: bevy::audio::PlaybackMode::Once,
+                    volume: Volume::new(0.4),
+                    ..default()
+                },
+            });
+
+            let safe_ui = safe_ui.get_single();
+            if let Ok(safe_ui) = safe_ui {
+                let mut safe_ui = commands.entity(safe_ui);
+                safe_ui.with_children(|ui| {
+                    ui.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                width: Val::Percent(30.),
+                                height: Val::Auto,
+                                left: Val::Percent(37.),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        UpgradeScreen::new(),
+                    ))
+                    .with_children(|screen| {
+                        screen
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    display: Display::Grid,
+                                    align_items: AlignItems::Center,
+                                    justify_items: JustifyItems::Center,
+                                    max_width: Val::Percent(100.),
+                                    height: Val::Percent(100.),
+                                    border: UiRect::all(Val::Px(5.)),
+                                    padding: UiRect::all(Val::Px(5.)),
+                                    ..default()
+                                },
+                                border_color: upgrade.color().into(),
+                                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                                ..default()
+                            })
+                            .with_children(|container| {
+                                container.spawn(
+                                    TextBundle::from_section(
+                                        display,
+                                        TextStyle {
+                                            font_size: 20.,
+                                            ..default()
+                                        },
+                                    )
+                                    .with_text_justify(JustifyText::Center),
+                                );
+                            });
+                    });
+                });
+                //next_state.set(InGameState::Upgrade);
+            }
+        }
+    }
+}
@@ -0,0 +1,372 @@
+#[cfg(not(feature = "bevy_mod_taa"))]
+use bevy::core_pipeline::experimental::taa::TemporalAntiAliasBundle as TAABundle;
+use bevy::{
+    app::PluginGroupBuilder,
+    asset::AssetPlugin,
+    audio::Volume,
+    input::InputPlugin,
+    prelude::*,
+    scene::ScenePlugin,
+    window::PresentMode,
+    winit::{UpdateMode, WinitSettings},
+};
+use bevy_ecs::system::EntityCommands;
+use bevy_embedded_assets::EmbeddedAssetPlugin;
+#[cfg(feature = "bevy_mod_taa")]
+use bevy_mod_taa::TAABundle;
+use bevy_obj::ObjPlugin;
+use bevy_rapier3d::prelude::*;
+use bevy_tnua::controller::TnuaControllerPlugin;
+use bevy_tnua_rapier3d::TnuaRapier3dPlugin;
+
+use leafwing_input_manager::plugin::InputManagerPlugin;
+use std::time::Duration;
+mod achievements;
+pub mod autoplay;
+mod benchmark;
+mod camera;
+mod checkpoints;
+mod daily;
+mod debug_overlay;
+mod diagnostics_recorder;
+mod discord;
+mod distance_posts;
+pub mod events;
+mod features;
+pub mod game_mode;
+pub mod generate;
+mod hazards;
+mod hud;
+pub mod input;
+mod level;
+mod level_diagnostics;
+mod level_upgrades;
+mod menu;
+mod pickups;
+mod platforms;
+mod player;
+mod scores;
+mod settings;
+mod share_code;
+mod skills;
+mod stats;
+mod system_info;
+pub mod upgrades;
+use crate::upgrades::*;
+const GAME_NAME: &str = "SkyScapade";
+/// Runs the game, or the benchmark when it was asked for on the command line.
+pub fn run() {
+    if benchmark::headless_requested() {
+        benchmark::run_headless();
+        return;
+    }
+    let mut app = App::new();
+    app.add_plugins(EmbeddedAssetPlugin {
+        mode: bevy_embedded_assets::PluginMode::ReplaceDefault,
+    });
+    app.add_plugins(discord::DiscordPlugin);
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: GAME_NAME.into(),
+                    //resolution: (2560.0, 1080.0).into(),
+                    resolution: (1280., 720.).into(),
+                    name: Some("new_game_1.app".into()),
+                    present_mode: PresentMode::AutoVsync,
+                    visible: false,
+                    ..default()
+                }),
+                ..default()
+            })
+            .set(ImagePlugin::default_nearest()),
+    )
+    .add_plugins(RapierDebugRenderPlugin {
+        enabled: false,
+        ..default()
+    })
+    .insert_resource(WinitSettings {
+        focused_mode: UpdateMode::Continuous,
+        unfocused_mode: UpdateMode::ReactiveLowPower {
+            wait: Duration::from_secs_f64(1.0 / 30.0), //Duration::MAX
+        },
+    })
+    .add_plugins(menu::MenuPlugin)
+    .add_plugins(settings::SettingsPlugin)
+    .add_plugins(ObjPlugin)
+    .insert_state(AppState::MainMenu)
+    //.add_plugins(ScreenDiagnosticsPlugin::default())
+    //.add_plugins(ScreenFrameDiagnosticsPlugin)
+    .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin)
+    .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+    //.add_plugins(bevy::diagnostic::SystemInformationDiagnosticsPlugin)
+    .add_plugins(system_info::SystemInformationDiagnosticsPlugin)
+    .add_plugins(level_diagnostics::LevelDiagnosticsPlugin)
+    .add_plugins(bevy_framepace::FramepacePlugin)
+    //.add_plugins(PerfUiPlugin)
+    .add_systems(Startup, setup)
+    .add_systems(Update, temp);
+    app.insert_state(InGameState::Playing);
+    app.add_plugins(GamePlugins);
+    app.add_plugins(scores::ScoresPlugin);
+    app.add_plugins(daily::DailyPlugin);
+    app.add_plugins(autoplay::AutoplayPlugin);
+    app.add_plugins(stats::StatsPlugin);
+    app.add_plugins(achievements::AchievementsPlugin);
+    app.add_plugins(debug_overlay::DebugOverlayPlugin);
+    app.add_plugins(diagnostics_recorder::DiagnosticsRecorderPlugin);
+    app.add_plugins(benchmark::BenchmarkPlugin);
+    app.run();
+}
+
+/// Everything needed to play a run, including its HUD, camera rig and sound effects. The
+/// window, menus, music and anything that touches files or other processes stay out, so tools
+/// and tests can assemble a game from it.
+pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(RapierPhysicsPlugin::<NoUserData>::default())
+            .add(InputManagerPlugin::<input::Action>::default())
+            .add(TnuaControllerPlugin::default())
+            .add(TnuaRapier3dPlugin::default())
+            .add(events::GameplayEventsPlugin)
+            .add(level::LevelPlugin)
+            .add(player::PlayerPlugin)
+            .add(hud::HudPlugin)
+            .add(level_upgrades::UpgradePlugin)
+            .add(camera::CameraPlugin)
+            .add(skills::SkillPlugin)
+            .add(hazards::HazardPlugin)
+            .add(features::FeaturePlugin)
+            .add(pickups::PickupPlugin)
+            .add(platforms::PlatformPlugin)
+            .add(game_mode::GameModePlugin)
+            .add(checkpoints::CheckpointPlugin)
+            .add(distance_posts::DistancePostPlugin)
+            .add(share_code::ShareCodePlugin)
+    }
+}
+
+/// An app with [`GamePlugins`] and no window, renderer or audio output, for the headless
+/// benchmark and tests. The caller adds the states and a [`generate::Generator`].
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        InputPlugin,
+        ScenePlugin,
+    ));
+    app.init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<Image>()
+        .init_asset::<AudioSource>();
+    app.add_plugins(GamePlugins);
+    app.init_resource::<discord::ActivityState>();
+    app.world.spawn(Camera3dBundle::default());
+    app
+}
+
+/// Starts a run on `generator` in a [`headless_app`] that steps 1/60 s per update.
+#[cfg(test)]
+pub fn headless_run(generator: generate::Generator) -> App {
+    let mut app = headless_app();
+    app.insert_resource(generator);
+    app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+        Duration::from_secs_f32(1. / 60.),
+    ));
+    app.insert_state(AppState::InGame);
+    app.insert_state(InGameState::Playing);
+    app.update();
+    app
+}
+
+fn interpolate(pa: f32, pb: f32, px: f32) -> f32 {
+    let ft = px * std::f32::consts::PI;
+    let f = (1. - ft.cos()) * 0.5;
+    pa * (1. - f) + pb * f
+}
+#[derive(Component)]
+struct SafeUi;
+trait UiHelper {
+    fn new_menu_button(&mut self, label: &str, component: impl Bundle) -> EntityCommands;
+}
+impl UiHelper for ChildBuilder<'_> {
+    fn new_menu_button(&mut self, label: &str, component: impl Bundle) -> EntityCommands {
+        let mut result = self.spawn((
+            ButtonBundle {
+                style: Style {
+                    display: Display::Grid,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE),
+                border_color: Color::BLACK.into(),
+                ..default()
+            },
+            component,
+        ));
+        result.with_children(|button| {
+            button.spawn(
+                TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        color: Color::RED,
+                        font_size: 22.0,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center)
+                .with_style(Style {
+                    align_self: AlignSelf::Center,
+                    justify_self: JustifySelf::Center,
+                    ..default()
+                }),
+            );
+        });
+        result
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    settings: Res<settings::SettingsResource>,
+    mut window: Query<&mut Window>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut window = window.single_mut();
+    window.visible = true;
+    // spawn a camera to be able to see anything
+    let mut camera = commands.spawn((Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 0.0, 0.0).looking_at(Vec3::new(0., 0.0, 0.), Vec3::Y),
+
+        ..default()
+    },));
+
+    if let settings::AntiAliasOption::Taa = settings.anti_alias {
+        camera.insert(TAABundle::default());
+    };
+    commands.insert_resource(AmbientLight {
+        color: Color::rgb_u8(234, 212, 165),
+        brightness: light_consts::lux::CLEAR_SUNRISE,
+    });
+    commands.spawn(AudioBundle {
+        source: asset_server.load("Neon Heights.mp3"),
+        settings: PlaybackSettings {
+            mode: bevy::audio::PlaybackMode::Loop,
+            volume: Volume::new(0.2),
+            ..default()
+        },
+    });
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+
+            //background_color: BackgroundColor(Color::WHITE),
+            ..default()
+        })
+        .with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        border: UiRect::all(Val::Px(1.)),
+                        height: Val::Percent(100.0),
+                        max_width: Val::Vw(100.0),
+                        aspect_ratio: Some(16.0 / 9.0),
+                        ..default()
+                    },
+                    //background_color: BackgroundColor(Color::RED),
+                    //border_color:Color::YELLOW.into(),
+                    ..default()
+                },
+                SafeUi,
+            ));
+        });
+}
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    MainMenu,
+    InGame,
+}
+
+fn temp(
+    input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<InGameState>>,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut next_app: ResMut<NextState<AppState>>,
+) {
+    if input.just_pressed(KeyCode::Escape) {
+        match state.get() {
+            InGameState::Playing => next_state.set(InGameState::Paused),
+            InGameState::Paused => next_state.set(InGameState::Playing),
+            InGameState::Upgrade => {}
+            InGameState::End => {
+                next_state.set(InGameState::None);
+                next_app.set(AppState::MainMenu);
+            }
+            InGameState::None => {}
+        }
+    }
+}
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InGameState {
+    Playing,
+    Paused,
+    Upgrade,
+    End,
+    None,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generate::NoiseSettings;
+
+    /// Starts a run with the gameplay plugins and no window or renderer.
+    #[test]
+    fn headless_run_starts() {
+        let mut app = headless_app();
+        app.insert_resource(generate::Generator::from_u64_seed(
+            0,
+            NoiseSettings::new(256_usize, 64, 5),
+            NoiseSettings::new(9_usize, 64, 3),
+        ));
+        app.insert_state(AppState::InGame);
+        app.insert_state(InGameState::Playing);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let level = app
+            .world
+            .query::<&level::Level>()
+            .get_single(&app.world)
+            .expect("the level was spawned");
+        assert!(level.right > 0);
+        let player = app
+            .world
+            .query_filtered::<&Parent, With<input::Player>>()
+            .get_single(&app.world)
+            .expect("the player was spawned");
+        let (level_entity, _) = app
+            .world
+            .query::<(Entity, &level::Level)>()
+            .single(&app.world);
+        assert_eq!(player.get(), level_entity);
+        assert_eq!(app.world.resource::<State<InGameState>>().get(), &InGameState::Playing);
+    }
+}
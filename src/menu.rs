@@ -1,4 +1,9 @@
+use crate::achievements::AchievementsResource;
+use crate::daily::{DailyChallenge, DailyHistoryResource, DailyRun};
+use crate::game_mode::GameMode;
 use crate::generate::NoiseSettings;
+use crate::share_code::{ShareCode, ShareCodeError};
+use crate::stats::load_run_stats;
 use crate::{discord::ActivityState, UiHelper};
 use crate::{generate, settings::*};
 use bevy::{app::AppExit, prelude::*};
@@ -18,6 +23,8 @@ impl Plugin for MenuPlugin {
                 main_menu_button_system,
                 settings_menu_button_system,
                 new_game_menu_system,
+                new_game_mode_button_system,
+                daily_challenge_button_system,
             ),
         );
         app.add_plugins(TextInputPlugin);
@@ -27,6 +34,8 @@ impl Plugin for MenuPlugin {
         app.add_systems(OnExit(MainMenuState::Settings), exit_settings);
         app.add_systems(OnEnter(MainMenuState::NewGame), enter_new_game);
         app.add_systems(OnExit(MainMenuState::NewGame), exit_new_game);
+        app.add_systems(OnEnter(MainMenuState::Stats), enter_stats);
+        app.add_systems(OnExit(MainMenuState::Stats), exit_stats);
     }
 }
 
@@ -36,10 +45,13 @@ pub enum MainMenuState {
     #[allow(dead_code)]
     NewGame,
     Settings,
+    Stats,
 }
 enum MainMenuButton {
     NewGame,
     Settings,
+    Stats,
+    Back,
     Exit,
 }
 #[derive(Component)]
@@ -122,6 +134,7 @@ fn new_game_menu_system(
     mut next_state: ResMut<NextState<crate::AppState>>,
     mut next_menu: ResMut<NextState<MainMenuState>>,
     mut text_query: Query<&mut Text>,
+    mut mode: ResMut<GameMode>,
 ) {
     for (interaction, children, mut color, mut border_color) in &mut interaction_query {
         let mut text = text_query.get_mut(children[0]).unwrap();
@@ -138,6 +151,20 @@ fn new_game_menu_system(
             }
             Interaction::Pressed => {
                 let mut text_input = text_input_query.single_mut();
+                // A pasted share code brings its own noise settings and mode along.
+                match ShareCode::decode(&text_input.0) {
+                    Ok(share_code) => {
+                        *mode = share_code.mode;
+                        // decode only accepts supported versions.
+                        commands.insert_resource(share_code.generator().unwrap());
+                        commands.remove_resource::<DailyRun>();
+                        next_state.set(crate::AppState::InGame);
+                        next_menu.set(MainMenuState::Menu);
+                        continue;
+                    }
+                    Err(ShareCodeError::MissingPrefix) => {}
+                    Err(err) => warn!("Treating input as a plain seed: {}", err),
+                }
                 let mut seed = [0_u8; 32];                
                 if text_input.0.is_empty() {
                     text_input.0 = thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect();
@@ -156,6 +183,7 @@ fn new_game_menu_system(
                     NoiseSettings::new(9_usize, 64, 3),
                 );
                 commands.insert_resource(generator);
+                commands.remove_resource::<DailyRun>();
                 next_state.set(crate::AppState::InGame);
                 next_menu.set(MainMenuState::Menu);
             }
@@ -163,6 +191,94 @@ fn new_game_menu_system(
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn daily_challenge_button_system(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &Children,
+            &mut BackgroundColor,
+            &mut BorderColor,
+        ),
+        (Changed<Interaction>, With<DailyChallengeButton>),
+    >,
+    history: Res<DailyHistoryResource>,
+    mut mode: ResMut<GameMode>,
+    mut next_state: ResMut<NextState<crate::AppState>>,
+    mut next_menu: ResMut<NextState<MainMenuState>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, children, mut color, mut border_color) in &mut interaction_query {
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        match *interaction {
+            Interaction::Hovered => {
+                *color = Color::RED.into();
+                border_color.0 = Color::WHITE;
+                text.sections[0].style.color = Color::WHITE;
+            }
+            Interaction::None => {
+                *color = Color::WHITE.into();
+                border_color.0 = Color::RED;
+                text.sections[0].style.color = Color::RED;
+            }
+            Interaction::Pressed => {
+                let challenge = DailyChallenge::today();
+                let generator = generate::Generator::from_seed(
+                    challenge.seed,
+                    challenge.height_noise_settings(),
+                    challenge.hole_noise_settings(),
+                );
+                // The daily challenge is always the standard timed run.
+                *mode = GameMode::Timed;
+                commands.insert_resource(generator);
+                commands.insert_resource(DailyRun {
+                    scored: !history.played(&challenge.date),
+                    challenge,
+                });
+                next_state.set(crate::AppState::InGame);
+                next_menu.set(MainMenuState::Menu);
+            }
+        }
+    }
+}
+
+fn new_game_mode_button_system(
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &Children,
+            &mut BackgroundColor,
+            &mut BorderColor,
+        ),
+        (Changed<Interaction>, With<NewGameModeButton>),
+    >,
+    mut mode: ResMut<GameMode>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, children, mut color, mut border_color) in &mut interaction_query {
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        match *interaction {
+            Interaction::Pressed => {
+                *color = Color::BLACK.into();
+                border_color.0 = Color::RED;
+                *mode = mode.next();
+                text.sections[0].value = mode.label().into();
+            }
+            Interaction::Hovered => {
+                *color = Color::RED.into();
+                border_color.0 = Color::WHITE;
+                text.sections[0].style.color = Color::WHITE;
+            }
+            Interaction::None => {
+                *color = Color::WHITE.into();
+                border_color.0 = Color::RED;
+                text.sections[0].style.color = Color::RED;
+            }
+        }
+    }
+}
+
 type ButtonInteractionFilter = (Changed<Interaction>, With<Button>);
 fn main_menu_button_system(
     mut interaction_query: Query<
@@ -189,6 +305,8 @@ fn main_menu_button_system(
                 match button.0 {
                     MainMenuButton::NewGame => next_menu.set(MainMenuState::NewGame),
                     MainMenuButton::Settings => next_menu.set(MainMenuState::Settings),
+                    MainMenuButton::Stats => next_menu.set(MainMenuState::Stats),
+                    MainMenuButton::Back => next_menu.set(MainMenuState::Menu),
                     MainMenuButton::Exit => {
                         exit.send(AppExit);
                     }
@@ -278,6 +396,10 @@ fn enter_main_menu(
                                 "Settings",
                                 MainMenuButtonComponent(MainMenuButton::Settings),
                             );
+                            parent.new_menu_button(
+                                "Stats",
+                                MainMenuButtonComponent(MainMenuButton::Stats),
+                            );
                             parent.new_menu_button(
                                 "Exit",
                                 MainMenuButtonComponent(MainMenuButton::Exit),
@@ -300,7 +422,17 @@ fn exit_new_game(new_game_menu: Query<Entity, With<NewGameMenu>>, mut commands:
         commands.entity(new_game_menu).despawn_recursive();
     }
 }
-fn enter_new_game(main_menu: Query<Entity, With<MainMenu>>, mut commands: Commands) {
+fn enter_new_game(
+    main_menu: Query<Entity, With<MainMenu>>,
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    daily_history: Res<DailyHistoryResource>,
+) {
+    let daily_label = if daily_history.played(&DailyChallenge::today().date) {
+        "Daily Challenge (Practice)"
+    } else {
+        "Daily Challenge"
+    };
     let main_menu = main_menu.get_single();
     if let Ok(main_menu) = main_menu {
         let mut main_menu = commands.entity(main_menu);
@@ -320,7 +452,7 @@ fn enter_new_game(main_menu: Query<Entity, With<MainMenu>>, mut commands: Comman
                         .spawn(NodeBundle::default())
                         .with_children(|seed_input| {
                             seed_input.spawn(TextBundle::from_section(
-                                "Seed: ",
+                                "Seed or code: ",
                                 TextStyle {
                                     color: Color::WHITE,
                                     font_size: 42.0,
@@ -330,7 +462,7 @@ fn enter_new_game(main_menu: Query<Entity, With<MainMenu>>, mut commands: Comman
                             seed_input.spawn((
                                 NodeBundle {
                                     style: Style {
-                                        width: Val::Px(200.0),
+                                        width: Val::Px(400.0),
                                         border: UiRect::all(Val::Px(2.0)),
                                         padding: UiRect::all(Val::Px(2.0)),
                                         ..default()
@@ -347,12 +479,108 @@ fn enter_new_game(main_menu: Query<Entity, With<MainMenu>>, mut commands: Comman
                                 }),
                             ));
                         });
+                    parent
+                        .spawn(NodeBundle::default())
+                        .with_children(|game_mode| {
+                            game_mode.spawn(TextBundle::from_section(
+                                "Mode: ",
+                                TextStyle {
+                                    color: Color::WHITE,
+                                    font_size: 42.0,
+                                    ..default()
+                                },
+                            ));
+                            game_mode.new_menu_button(mode.label(), NewGameModeButton);
+                        });
                     parent.new_menu_button("Start", NewGameStartButton);
+                    parent.new_menu_button(daily_label, DailyChallengeButton);
                 });
         });
     }
 }
 
+/// Totals over every run in the statistics log, the most recent runs and the achievements.
+fn enter_stats(
+    main_menu: Query<Entity, With<MainMenu>>,
+    mut commands: Commands,
+    achievements: Res<AchievementsResource>,
+) {
+    let runs = load_run_stats();
+    let mut summary = if runs.is_empty() {
+        "No runs recorded yet".to_string()
+    } else {
+        format!(
+            "Runs: {}\nBest distance: {:.0}\nTop speed: {:.1}\nJumps: {} - Air jumps: {}\nDashes: {} - Glides: {}\nNear misses: {}\n\nRecent runs",
+            runs.len(),
+            runs.iter().map(|run| run.distance).fold(0., f32::max),
+            runs.iter().map(|run| run.top_speed).fold(0., f32::max),
+            runs.iter().map(|run| run.jumps).sum::<u32>(),
+            runs.iter().map(|run| run.air_jumps).sum::<u32>(),
+            runs.iter().map(|run| run.dashes).sum::<u32>(),
+            runs.iter().map(|run| run.glides).sum::<u32>(),
+            runs.iter().map(|run| run.near_misses).sum::<u32>(),
+        )
+    };
+    for run in runs.iter().rev().take(5) {
+        summary += &format!(
+            "\n{} ({}): {:.0} in {:.0}s",
+            run.seed,
+            run.mode.label(),
+            run.distance,
+            run.duration
+        );
+    }
+    summary += "\n\nAchievements";
+    for (achievement, unlocked) in achievements.all() {
+        summary += &format!(
+            "\n[{}] {} - {}",
+            if unlocked { "x" } else { " " },
+            achievement.title(),
+            achievement.description()
+        );
+    }
+    // The summary spans several lines, more than the fixed menu rows fit.
+    let mut stats_bundle = get_main_menu_menu_bundle();
+    stats_bundle.style.grid_template_rows = vec![
+        GridTrack::px(100.0),
+        GridTrack::auto(),
+        GridTrack::px(32.0),
+    ];
+    let main_menu = main_menu.get_single();
+    if let Ok(main_menu) = main_menu {
+        let mut main_menu = commands.entity(main_menu);
+        main_menu.with_children(|menu_base| {
+            menu_base
+                .spawn((StatsMenu, stats_bundle))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Stats",
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 42.0,
+                            ..default()
+                        },
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        summary,
+                        TextStyle {
+                            color: Color::WHITE,
+                            font_size: 22.0,
+                            ..default()
+                        },
+                    ));
+                    parent.new_menu_button("Back", MainMenuButtonComponent(MainMenuButton::Back));
+                });
+        });
+    }
+}
+fn exit_stats(stats_menu: Query<Entity, With<StatsMenu>>, mut commands: Commands) {
+    let stats_menu = stats_menu.get_single();
+    if let Ok(stats_menu) = stats_menu {
+        commands.entity(stats_menu).despawn_recursive();
+    }
+}
+
 fn enter_settings(
     main_menu: Query<Entity, With<MainMenu>>,
     mut commands: Commands,
@@ -442,11 +670,17 @@ struct MainMenu;
 struct SettingsMenu;
 #[derive(Component)]
 struct NewGameMenu;
+#[derive(Component)]
+struct StatsMenu;
 
 #[derive(Component)]
 struct NewGameSeedInput;
 #[derive(Component)]
 struct NewGameStartButton;
+#[derive(Component)]
+struct NewGameModeButton;
+#[derive(Component)]
+struct DailyChallengeButton;
 enum SettingsMenuButton {
     Apply,
     FrameLimit,
@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use bevy::{audio::Volume, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::{generate::Pickup, input::Player, level::Level, AppState, InGameState};
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickupAssets>();
+        app.add_systems(
+            Update,
+            (collect_pickups, spin_pickups)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+const TIME_SHARD_BONUS: Duration = Duration::from_secs(5);
+const SCORE_ORB_BONUS: f32 = 100.;
+
+#[derive(Resource, Clone)]
+pub struct PickupAssets {
+    orb_mesh: Handle<Mesh>,
+    crate_mesh: Handle<Mesh>,
+    time_shard_material: Handle<StandardMaterial>,
+    score_orb_material: Handle<StandardMaterial>,
+    upgrade_crate_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for PickupAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let orb_mesh = meshes.add(Sphere::new(0.4));
+        let crate_mesh = meshes.add(Cuboid::new(1., 1., 1.));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            orb_mesh,
+            crate_mesh,
+            time_shard_material: materials.add(StandardMaterial {
+                base_color: Color::CYAN,
+                emissive: Color::CYAN,
+                ..default()
+            }),
+            score_orb_material: materials.add(StandardMaterial {
+                base_color: Color::GOLD,
+                emissive: Color::GOLD,
+                ..default()
+            }),
+            upgrade_crate_material: materials.add(StandardMaterial {
+                base_color: Color::PURPLE,
+                emissive: Color::PURPLE,
+                ..default()
+            }),
+        }
+    }
+}
+
+#[derive(Component)]
+struct PickupSensor(Pickup);
+
+/// Spawns a pickup floating above the floor cube at `x`, `y`.
+pub fn spawn_pickup(
+    commands: &mut Commands,
+    assets: &PickupAssets,
+    level: Entity,
+    pickup: Pickup,
+    x: f32,
+    y: f32,
+) {
+    let (mesh, material) = match pickup {
+        Pickup::TimeShard => (
+            assets.orb_mesh.clone(),
+            assets.time_shard_material.clone(),
+        ),
+        Pickup::ScoreOrb => (
+            assets.orb_mesh.clone(),
+            assets.score_orb_material.clone(),
+        ),
+        Pickup::UpgradeCrate => (
+            assets.crate_mesh.clone(),
+            assets.upgrade_crate_material.clone(),
+        ),
+    };
+    commands
+        .spawn((
+            PickupSensor(pickup),
+            Collider::ball(0.6),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            PbrBundle {
+                mesh,
+                material,
+                transform: Transform::from_xyz(x, y + 3., 0.),
+                ..default()
+            },
+        ))
+        .set_parent(level);
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    sensors: Query<&PickupSensor>,
+    mut player: Query<(Entity, &mut Player)>,
+    mut level: Query<&mut Level>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((player_entity, mut player)) = player.get_single_mut() else {
+        return;
+    };
+    let Ok(mut level) = level.get_single_mut() else {
+        return;
+    };
+    for event in collision_events.read() {
+        let CollisionEvent::Started(a, b, _) = event else {
+            continue;
+        };
+        let other = if *a == player_entity {
+            *b
+        } else if *b == player_entity {
+            *a
+        } else {
+            continue;
+        };
+        let Ok(sensor) = sensors.get(other) else {
+            continue;
+        };
+        match sensor.0 {
+            Pickup::TimeShard => {
+                let duration = level.timer.duration() + TIME_SHARD_BONUS;
+                level.timer.set_duration(duration);
+            }
+            Pickup::ScoreOrb => {
+                player.bonus_score += SCORE_ORB_BONUS;
+            }
+            Pickup::UpgradeCrate => {
+                level.upgrade_crates += 1;
+            }
+        }
+        commands.entity(other).despawn_recursive();
+        commands.spawn(AudioBundle {
+            source: asset_server.load("upgrade.mp3"),
+            settings: PlaybackSettings {
+                mode: bevy::audio::PlaybackMode::Despawn,
+                volume: Volume::new(0.15),
+                speed: 2.,
+                ..default()
+            },
+        });
+    }
+}
+
+fn spin_pickups(time: Res<Time>, mut pickups: Query<&mut Transform, With<PickupSensor>>) {
+    for mut transform in pickups.iter_mut() {
+        transform.rotate_y(time.delta_seconds() * 2.);
+    }
+}
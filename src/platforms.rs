@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    generate::{Platform, PlatformMotion, PLATFORM_HALF_THICKNESS},
+    AppState, InGameState,
+};
+
+pub struct PlatformPlugin;
+
+impl Plugin for PlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FloatingPlatformAssets>();
+        app.add_systems(
+            FixedUpdate,
+            move_platforms
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+/// How hard a moving platform is pulled back onto its path when physics lets it drift.
+const PATH_CORRECTION: f32 = 5.;
+
+#[derive(Resource, Clone)]
+pub struct FloatingPlatformAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for FloatingPlatformAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Cuboid::new(
+            3.,
+            PLATFORM_HALF_THICKNESS * 2.,
+            2.,
+        ));
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::rgb_u8(90, 110, 140),
+                ..default()
+            });
+        Self { mesh, material }
+    }
+}
+
+#[derive(Component)]
+struct MovingPlatform {
+    origin: Vec3,
+    motion: PlatformMotion,
+    elapsed: f32,
+}
+
+impl MovingPlatform {
+    fn direction(&self) -> Vec3 {
+        if self.motion.vertical {
+            Vec3::Y
+        } else {
+            Vec3::X
+        }
+    }
+    fn angular_frequency(&self) -> f32 {
+        std::f32::consts::TAU / self.motion.period
+    }
+    fn position(&self) -> Vec3 {
+        let angle = self.angular_frequency() * self.elapsed + self.motion.phase;
+        self.origin + self.direction() * self.motion.amplitude * angle.sin()
+    }
+    fn velocity(&self) -> Vec3 {
+        let angle = self.angular_frequency() * self.elapsed + self.motion.phase;
+        self.direction() * self.motion.amplitude * self.angular_frequency() * angle.cos()
+    }
+}
+
+/// Spawns a floating platform for the column whose floor cube is at `x`, `y`. Moving platforms
+/// are velocity based kinematic bodies so Tnua carries the player along with them.
+pub fn spawn_platform(
+    commands: &mut Commands,
+    assets: &FloatingPlatformAssets,
+    level: Entity,
+    platform: Platform,
+    x: f32,
+    y: f32,
+) {
+    let origin = Vec3::new(x + platform.offset_x * 2., y + platform.offset_y, 0.);
+    let moving = platform.motion.map(|motion| MovingPlatform {
+        origin,
+        motion,
+        elapsed: 0.,
+    });
+    let translation = moving.as_ref().map_or(origin, MovingPlatform::position);
+    let mut entity = commands.spawn((
+        Collider::cuboid(1.5, PLATFORM_HALF_THICKNESS, 1.),
+        crate::level::LevelFloor,
+        PbrBundle {
+            mesh: assets.mesh.clone(),
+            material: assets.material.clone(),
+            transform: Transform::from_translation(translation),
+            ..default()
+        },
+    ));
+    if let Some(moving) = moving {
+        entity.insert((
+            RigidBody::KinematicVelocityBased,
+            Velocity::linear(moving.velocity()),
+            moving,
+        ));
+    }
+    entity.set_parent(level);
+}
+
+fn move_platforms(
+    time: Res<Time>,
+    mut platforms: Query<(&mut MovingPlatform, &Transform, &mut Velocity)>,
+) {
+    for (mut platform, transform, mut velocity) in platforms.iter_mut() {
+        platform.elapsed += time.delta_seconds();
+        let drift = platform.position() - transform.translation;
+        velocity.linvel = platform.velocity() + drift * PATH_CORRECTION;
+    }
+}
@@ -0,0 +1,280 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier3d::prelude::*;
+use bevy_tnua::{
+    builtins::{TnuaBuiltinJump, TnuaBuiltinWalk},
+    control_helpers::TnuaSimpleAirActionsCounter,
+    controller::{TnuaController, TnuaControllerBundle},
+    TnuaAction,
+};
+use bevy_tnua_rapier3d::{TnuaRapier3dIOBundle, TnuaRapier3dSensorShape};
+use leafwing_input_manager::{
+    action_state::ActionState, axislike::DualAxis, input_map::InputMap, InputManagerBundle,
+};
+
+use crate::{
+    events, input,
+    input::Player,
+    level::{start_level, Level, PlatformAssets},
+    upgrades::{JumpSkill, UpgradeLevel},
+    AppState, InGameState,
+};
+
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), spawn_player.after(start_level));
+        app.add_systems(
+            Update,
+            move_player.run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (update_distance, glide_cooldown)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+/// How high Tnua keeps the player's center above the ground.
+pub const FLOAT_HEIGHT: f32 = 2.;
+/// Gap between the ground and the bottom of the standing player's capsule, anything on the floor
+/// the player should walk into has to reach higher than this.
+pub const FLOAT_GAP: f32 = FLOAT_HEIGHT - (1. + 0.4);
+
+/// Spawns the player on the level's spawn point with the default key and gamepad bindings.
+pub fn spawn_player(
+    mut commands: Commands,
+    level: Query<(Entity, &Level)>,
+    platform_assets: Res<PlatformAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let (level, level_state) = level.single();
+    let mut input_map = InputMap::default();
+    input_map.insert(input::Action::Jump, KeyCode::Space);
+    input_map.insert(input::Action::Jump, GamepadButtonType::South);
+    input_map.insert(input::Action::Left, KeyCode::KeyA);
+    input_map.insert(input::Action::Right, KeyCode::KeyD);
+    input_map.insert(input::Action::Move, DualAxis::left_stick());
+    input_map.insert(input::Action::Dash, KeyCode::ShiftLeft);
+    input_map.insert(input::Action::Dash, GamepadButtonType::West);
+    input_map.insert(input::Action::Accept, KeyCode::Enter);
+    input_map.insert(input::Action::Accept, GamepadButtonType::South);
+    input_map.insert(input::Action::Glide, KeyCode::KeyW);
+    input_map.insert(input::Action::Glide, GamepadButtonType::North);
+    input_map.insert(input::Action::TimeSlow, KeyCode::KeyQ);
+    input_map.insert(input::Action::TimeSlow, GamepadButtonType::East);
+
+    let player_mesh = meshes.add(Capsule3d::new(0.4, 2.));
+    commands
+        .spawn(Collider::capsule_y(1., 0.4))
+        .insert(PbrBundle {
+            mesh: player_mesh,
+            material: platform_assets.material.clone(),
+            ..default()
+        })
+        .insert(TnuaRapier3dSensorShape(Collider::ball(0.4)))
+        .insert(TnuaControllerBundle::default())
+        .insert(TnuaRapier3dIOBundle::default())
+        .insert(ColliderMassProperties::Density(1.0))
+        .insert(input::Player {
+            base_speed: input::BASE_SPEED,
+            base_jump_power: input::BASE_JUMP_POWER,
+            speed_modifiers: vec![],
+            jump_modifiers: vec![],
+            jump_skill: JumpSkill {
+                max_jumps: 1,
+                tier: UpgradeLevel::None,
+                air: false,
+            },
+            ..default()
+        })
+        .insert(InputManagerBundle::with_map(input_map))
+        .insert(TnuaSimpleAirActionsCounter::default())
+        .insert(RigidBody::Dynamic)
+        .insert(LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Z)
+        .insert(TransformBundle::from(Transform::from_translation(
+            level_state.checkpoint,
+        )))
+        .set_parent(level);
+}
+
+fn update_distance(mut player: Query<(&Transform, &mut Player)>) {
+    let (player_transform, mut player) = player.single_mut();
+    if player_transform.translation.x > player.distance {
+        player.distance = player_transform.translation.x;
+    }
+    player.score = player.distance + player.bonus_score;
+}
+
+fn glide_cooldown(mut player: Query<&mut Player>, time: Res<Time>) {
+    let mut player = player.single_mut();
+    if let Some(ref mut cooldown) = player.glide_cooldown {
+        cooldown.tick(time.delta());
+        if cooldown.just_finished() {
+            player.used_glides -= 1;
+            if player.used_glides == 0 {
+                player.glide_cooldown = None;
+            } else {
+                player.glide_cooldown =
+                    Some(Timer::new(player.glide_skill.cooldown, TimerMode::Once));
+            }
+        }
+    }
+}
+
+/// The glider model shown while gliding.
+#[derive(Component)]
+pub struct Glider;
+
+const SHORT_HOP_EXTRA_GRAVITY: f32 = 80.;
+
+#[derive(SystemParam)]
+pub struct MovementEvents<'w> {
+    skills: EventWriter<'w, events::SkillUsed>,
+    landed: EventWriter<'w, events::PlayerLanded>,
+}
+pub fn move_player(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &ActionState<input::Action>,
+        &mut TnuaController,
+        &mut input::Player,
+        &mut TnuaSimpleAirActionsCounter,
+    )>,
+    glider_query: Query<Entity, With<Glider>>,
+    asset_server: ResMut<AssetServer>,
+    time: Res<Time>,
+    movement_events: MovementEvents,
+    mut was_airborne: Local<bool>,
+) {
+    let MovementEvents {
+        mut skills,
+        mut landed,
+    } = movement_events;
+    let (
+        player_entity,
+        player_transform,
+        action_state,
+        mut controller,
+        mut player,
+        mut air_actions_counter,
+    ) = query.single_mut();
+    // Each action has a button-like state of its own that you can check
+    //println!("move_player {:?}",action_state);
+    air_actions_counter.update(controller.as_mut());
+    let mut direction = Vec3::ZERO;
+    if action_state.pressed(&input::Action::Left) {
+        direction -= Vec3::X;
+    }
+    if action_state.pressed(&input::Action::Right) {
+        direction += Vec3::X;
+    }
+    if action_state.pressed(&input::Action::Move) {
+        let xy = 0.2
+            + action_state
+                .clamped_axis_pair(&input::Action::Move)
+                .unwrap()
+                .xy();
+        direction = Vec3::new(xy.x, 0., 0.)
+    }
+    controller.basis(TnuaBuiltinWalk {
+        desired_velocity: direction.clamp(-Vec3::X, Vec3::X) * player.max_speed(),
+        desired_forward: direction.normalize_or_zero(),
+        float_height: FLOAT_HEIGHT,
+        ..Default::default()
+    });
+    if controller.is_airborne().unwrap()
+        && action_state.pressed(&input::Action::Glide)
+        && (match &player.glide_timer {
+            Some(timer) => !timer.finished(),
+            None => true,
+        })
+        && player.used_glides < player.glide_skill.max_uses
+    {
+        if action_state.just_pressed(&input::Action::Glide) {
+            player.used_glides += 1;
+            skills.send(events::SkillUsed(events::Skill::Glide));
+            player.glide_timer = Some(Timer::new(player.glide_skill.max_duration, TimerMode::Once));
+            let mut player_entity = commands.entity(player_entity);
+            let glider_scene: Handle<Scene> = asset_server.load("glider.glb#Scene0");
+            player_entity.with_children(|child| {
+                child.spawn((
+                    Glider,
+                    SceneBundle {
+                        scene: glider_scene,
+                        transform: Transform::from_xyz(0., 2., 0.),
+                        ..default()
+                    },
+                ));
+            });
+        } else if let Some(timer) = &mut player.glide_timer {
+            timer.tick(time.delta());
+        }
+        if player.glide_cooldown.is_none() {
+            player.glide_cooldown = Some(Timer::new(player.glide_skill.cooldown, TimerMode::Once))
+        }
+        controller.action(TnuaBuiltinJump {
+            height: 0.1,
+            fall_extra_gravity: -5.,
+            allow_in_air: true,
+            ..default()
+        });
+    }
+    let mut glide_over = player.glide_timer.is_some()
+        && (!controller.is_airborne().unwrap()
+            || action_state.just_released(&input::Action::Glide)
+            || match &player.glide_timer {
+                Some(timer) => timer.finished(),
+                None => false,
+            });
+    let air_count = air_actions_counter.air_count_for(TnuaBuiltinJump::NAME);
+    let airborne = controller.is_airborne().unwrap();
+    if !airborne {
+        player.jump_index = 0;
+        if *was_airborne {
+            landed.send(events::PlayerLanded {
+                position: player_transform.translation,
+            });
+        }
+    }
+    *was_airborne = airborne;
+    if action_state.pressed(&input::Action::Jump) {
+        let air_jumps: usize = (player.jump_skill.max_jumps - 1).into();
+        let allow_in_air = player.jump_skill.air && air_count <= air_jumps;
+        if action_state.just_pressed(&input::Action::Jump) && airborne && allow_in_air {
+            player.jump_index = air_count.max(player.jump_index + 1);
+            skills.send(events::SkillUsed(events::Skill::AirJump {
+                index: player.jump_index,
+            }));
+        } else if action_state.just_pressed(&input::Action::Jump) && !airborne {
+            skills.send(events::SkillUsed(events::Skill::Jump));
+        }
+        glide_over = player.glide_timer.is_some();
+        // Releasing jump early stops feeding the action, which Tnua treats as a short hop.
+        let jump_power = player.jump_power();
+        controller.action(TnuaBuiltinJump {
+            height: player.jump_skill.jump_height(jump_power, player.jump_index),
+            allow_in_air,
+            shorten_extra_gravity: SHORT_HOP_EXTRA_GRAVITY,
+            ..default()
+        });
+    }
+
+    if glide_over {
+        player.glide_timer = None;
+        if let Ok(glider) = glider_query.get_single() {
+            commands.entity(glider).despawn_recursive();
+        }
+        if !action_state.pressed(&input::Action::Jump) {
+            controller.action(TnuaBuiltinJump {
+                height: 0.1,
+                fall_extra_gravity: 20.,
+                allow_in_air: true,
+                ..default()
+            });
+        }
+    }
+}
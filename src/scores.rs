@@ -0,0 +1,87 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy_persistent::{Persistent, StorageFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_mode::GameMode, generate::Generator, input::Player, level::Level, InGameState,
+};
+
+pub struct ScoresPlugin;
+
+impl Plugin for ScoresPlugin {
+    fn build(&self, app: &mut App) {
+        let config_dir = crate::benchmark::profile_dir();
+        app.insert_resource(
+            ScoresResource::builder()
+                .name("scores")
+                .format(StorageFormat::Toml)
+                .path(config_dir.join("scores.toml"))
+                .default(ScoreHistory::default())
+                .build()
+                .expect("Failed to load scores"),
+        );
+        app.add_systems(OnEnter(InGameState::End), record_score);
+    }
+}
+
+pub type ScoresResource = Persistent<ScoreHistory>;
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct ScoreHistory {
+    #[serde(default)]
+    pub runs: Vec<ScoreRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScoreRecord {
+    pub seed: String,
+    /// Scores from before versioning were all made by version 1.
+    #[serde(default = "first_generator_version")]
+    pub generator_version: u8,
+    #[serde(default)]
+    pub mode: GameMode,
+    pub score: f32,
+    #[serde(default)]
+    pub respawns: u32,
+    /// Seconds since the unix epoch when the run ended.
+    pub finished: u64,
+}
+
+pub fn first_generator_version() -> u8 {
+    1
+}
+
+/// The seed as typed in the New Game menu, without the zero padding.
+pub fn seed_label(seed: &[u8; 32]) -> String {
+    String::from_utf8_lossy(seed)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+fn record_score(
+    mut scores: ResMut<ScoresResource>,
+    player: Query<&Player>,
+    level: Query<&Level>,
+    generator: Res<Generator>,
+    mode: Res<GameMode>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let record = ScoreRecord {
+        seed: seed_label(&generator.get_seed()),
+        generator_version: generator.version(),
+        mode: *mode,
+        score: player.score,
+        respawns: level.get_single().map_or(0, |level| level.respawns),
+        finished: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    };
+    info!("Recording score: {:?}", record);
+    if let Err(err) = scores.update(|scores| scores.runs.push(record.clone())) {
+        error!("Failed to save score: {}", err);
+    }
+}
@@ -53,6 +53,9 @@ pub struct Settings {
     pub window_mode: WindowModeOption,
     #[serde(default)]
     pub anti_alias: AntiAliasOption,
+    /// Overrides the Discord application the rich presence shows up as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discord_client_id: Option<String>,
 }
 
 pub trait SettingsCycleOption {
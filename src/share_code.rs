@@ -0,0 +1,305 @@
+use std::fmt::Display;
+
+use bevy::prelude::*;
+
+use crate::{
+    game_mode::GameMode,
+    generate::{
+        Generator, NoiseSettings, UnsupportedGeneratorVersion, SUPPORTED_GENERATOR_VERSIONS,
+    },
+};
+
+pub struct ShareCodePlugin;
+
+impl Plugin for ShareCodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, share_code_display);
+    }
+}
+
+/// HUD text showing the share code of the level being played.
+#[derive(Component)]
+pub struct ShareCodeDisplay;
+
+fn share_code_display(
+    generator: Res<Generator>,
+    mode: Res<GameMode>,
+    mut text: Query<&mut Text, Added<ShareCodeDisplay>>,
+) {
+    for mut text in text.iter_mut() {
+        text.sections[0].value = format!(
+            "Code: {}",
+            ShareCode::from_generator(&generator, *mode).encode()
+        );
+    }
+}
+
+/// Everything needed to reproduce a level, packed into a copy-pasteable string.
+///
+/// The payload is `version, mode, seed length, seed, height noise, hole noise` followed by a
+/// Fletcher-16 checksum, written in Crockford base32 behind [`ShareCode::PREFIX`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareCode {
+    pub version: u8,
+    pub mode: GameMode,
+    pub seed: [u8; 32],
+    pub height_noise: NoiseSettings,
+    pub hole_noise: NoiseSettings,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ShareCodeError {
+    MissingPrefix,
+    InvalidCharacter(char),
+    Truncated,
+    Checksum,
+    UnknownVersion(u8),
+    UnknownMode(u8),
+    InvalidNoise,
+}
+impl Display for ShareCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::MissingPrefix => write!(f, "not a share code"),
+            ShareCodeError::InvalidCharacter(char) => write!(f, "invalid character '{}'", char),
+            ShareCodeError::Truncated => write!(f, "share code is incomplete"),
+            ShareCodeError::Checksum => write!(f, "share code checksum does not match"),
+            ShareCodeError::UnknownVersion(version) => {
+                write!(f, "unknown generator version {}", version)
+            }
+            ShareCodeError::UnknownMode(mode) => write!(f, "unknown game mode {}", mode),
+            ShareCodeError::InvalidNoise => write!(f, "share code has invalid noise settings"),
+        }
+    }
+}
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl ShareCode {
+    pub const PREFIX: &'static str = "SKY";
+
+    pub fn from_generator(generator: &Generator, mode: GameMode) -> Self {
+        Self {
+            version: generator.version(),
+            mode,
+            seed: generator.get_seed(),
+            height_noise: generator.height_noise_settings(),
+            hole_noise: generator.hole_noise_settings(),
+        }
+    }
+
+    pub fn generator(&self) -> Result<Generator, UnsupportedGeneratorVersion> {
+        Generator::from_seed_versioned(self.seed, self.version, self.height_noise, self.hole_noise)
+    }
+
+    pub fn encode(&self) -> String {
+        let seed_length = self
+            .seed
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |i| i + 1);
+        let mut bytes = vec![self.version, mode_to_byte(self.mode), seed_length as u8];
+        bytes.extend_from_slice(&self.seed[..seed_length]);
+        for noise in [self.height_noise, self.hole_noise] {
+            bytes.extend_from_slice(&(noise.wave_length() as u32).to_le_bytes());
+            bytes.extend_from_slice(&noise.amplitude().to_le_bytes());
+            bytes.push(noise.octaves() as u8);
+        }
+        bytes.extend_from_slice(&fletcher16(&bytes).to_le_bytes());
+        format!("{}{}", Self::PREFIX, base32_encode(&bytes))
+    }
+
+    pub fn decode(code: &str) -> Result<Self, ShareCodeError> {
+        let code = code.trim();
+        let prefix = code
+            .get(..Self::PREFIX.len())
+            .ok_or(ShareCodeError::MissingPrefix)?;
+        if !prefix.eq_ignore_ascii_case(Self::PREFIX) {
+            return Err(ShareCodeError::MissingPrefix);
+        }
+        let bytes = base32_decode(&code[Self::PREFIX.len()..])?;
+        let (payload, checksum) = bytes
+            .split_last_chunk::<2>()
+            .ok_or(ShareCodeError::Truncated)?;
+        if fletcher16(payload) != u16::from_le_bytes(*checksum) {
+            return Err(ShareCodeError::Checksum);
+        }
+        let mut reader = payload.iter().copied();
+        let mut next = || reader.next().ok_or(ShareCodeError::Truncated);
+        let version = next()?;
+        if !SUPPORTED_GENERATOR_VERSIONS.contains(&version) {
+            return Err(ShareCodeError::UnknownVersion(version));
+        }
+        let mode_byte = next()?;
+        let mode = mode_from_byte(mode_byte).ok_or(ShareCodeError::UnknownMode(mode_byte))?;
+        let seed_length = next()? as usize;
+        if seed_length > 32 {
+            return Err(ShareCodeError::Truncated);
+        }
+        let mut seed = [0_u8; 32];
+        for byte in seed.iter_mut().take(seed_length) {
+            *byte = next()?;
+        }
+        let mut noise = || -> Result<NoiseSettings, ShareCodeError> {
+            let mut wave_length = [0_u8; 4];
+            for byte in wave_length.iter_mut() {
+                *byte = next()?;
+            }
+            let mut amplitude = [0_u8; 8];
+            for byte in amplitude.iter_mut() {
+                *byte = next()?;
+            }
+            Ok(NoiseSettings::new(
+                u32::from_le_bytes(wave_length) as usize,
+                f64::from_le_bytes(amplitude),
+                next()? as usize,
+            ))
+        };
+        let height_noise = noise()?;
+        let hole_noise = noise()?;
+        if !height_noise.is_valid() || !hole_noise.is_valid() {
+            return Err(ShareCodeError::InvalidNoise);
+        }
+        Ok(Self {
+            version,
+            mode,
+            seed,
+            height_noise,
+            hole_noise,
+        })
+    }
+}
+
+fn mode_to_byte(mode: GameMode) -> u8 {
+    match mode {
+        GameMode::Timed => 0,
+        GameMode::Endless => 1,
+        GameMode::Survival => 2,
+        GameMode::Practice => 3,
+        GameMode::ChaseWall => 4,
+    }
+}
+fn mode_from_byte(byte: u8) -> Option<GameMode> {
+    match byte {
+        0 => Some(GameMode::Timed),
+        1 => Some(GameMode::Endless),
+        2 => Some(GameMode::Survival),
+        3 => Some(GameMode::Practice),
+        4 => Some(GameMode::ChaseWall),
+        _ => None,
+    }
+}
+
+fn fletcher16(bytes: &[u8]) -> u16 {
+    let (mut low, mut high) = (0_u16, 0_u16);
+    for byte in bytes {
+        low = (low + *byte as u16) % 255;
+        high = (high + low) % 255;
+    }
+    (high << 8) | low
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        result.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    result
+}
+
+/// Crockford base32 is case insensitive, reads `I`/`L` as `1` and `O` as `0`, and ignores dashes.
+fn base32_decode(text: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let mut result = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for char in text.chars().filter(|char| *char != '-') {
+        let char = match char.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            char => char,
+        };
+        let value = ALPHABET
+            .iter()
+            .position(|symbol| *symbol as char == char)
+            .ok_or(ShareCodeError::InvalidCharacter(char))? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::GENERATOR_VERSION;
+    fn share_code(seed: &[u8]) -> ShareCode {
+        let mut padded = [0_u8; 32];
+        padded[..seed.len()].copy_from_slice(seed);
+        ShareCode {
+            version: GENERATOR_VERSION,
+            mode: GameMode::Survival,
+            seed: padded,
+            height_noise: NoiseSettings::new(256_usize, 64, 5),
+            hole_noise: NoiseSettings::new(9_usize, 64, 3),
+        }
+    }
+    #[test]
+    fn round_trip() {
+        for seed in [&b"hello"[..], &[0xff; 32][..], &b""[..]] {
+            let code = share_code(seed);
+            assert_eq!(ShareCode::decode(&code.encode()), Ok(code));
+        }
+    }
+    #[test]
+    fn lenient_input() {
+        let code = share_code(b"hello");
+        let text = code.encode().to_lowercase().replace('1', "l");
+        assert_eq!(ShareCode::decode(&format!("  {}  ", text)), Ok(code));
+    }
+    #[test]
+    fn rejects_typos() {
+        let text = share_code(b"hello").encode();
+        let typo = ShareCode::PREFIX.len() + 4;
+        let replacement = if &text[typo..=typo] == "A" { "B" } else { "A" };
+        let text = format!("{}{}{}", &text[..typo], replacement, &text[typo + 1..]);
+        assert_eq!(ShareCode::decode(&text), Err(ShareCodeError::Checksum));
+        assert_eq!(
+            ShareCode::decode("hello"),
+            Err(ShareCodeError::MissingPrefix)
+        );
+        assert_eq!(
+            ShareCode::decode("SKÄY1"),
+            Err(ShareCodeError::MissingPrefix)
+        );
+    }
+    #[test]
+    fn rejects_invalid_noise() {
+        let invalid = [
+            NoiseSettings::new(0_usize, 64, 3),
+            NoiseSettings::new(9_usize, f64::NAN, 3),
+            NoiseSettings::new(9_usize, 64, 200),
+        ];
+        for noise in invalid {
+            let code = ShareCode {
+                hole_noise: noise,
+                ..share_code(b"hello")
+            };
+            assert_eq!(
+                ShareCode::decode(&code.encode()),
+                Err(ShareCodeError::InvalidNoise)
+            );
+        }
+    }
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 mod dash;
+mod jump;
+mod time_slow;
 
 pub struct SkillPlugin;
 
 impl Plugin for SkillPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(dash::DashPlugin);
+        app.add_plugins((
+            dash::DashPlugin,
+            jump::JumpPlugin,
+            time_slow::TimeSlowPlugin,
+        ));
     }
 }
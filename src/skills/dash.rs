@@ -6,7 +6,11 @@ use bevy_tnua::{
 };
 use leafwing_input_manager::action_state::ActionState;
 
-use crate::{input::*, AppState, InGameState};
+use crate::{
+    events::{Skill, SkillUsed},
+    input::*,
+    AppState, InGameState,
+};
 pub struct DashPlugin;
 
 impl Plugin for DashPlugin {
@@ -19,7 +23,10 @@ impl Plugin for DashPlugin {
     }
 }
 
-fn dash(mut query: Query<(&ActionState<Action>, &mut TnuaController, &mut Player)>) {
+fn dash(
+    mut query: Query<(&ActionState<Action>, &mut TnuaController, &mut Player)>,
+    mut skills: EventWriter<SkillUsed>,
+) {
     let (action_state, mut controller, mut player) = query.single_mut();
 
     if controller.dynamic_basis().is_some() && (!controller.is_airborne().unwrap() || player.dash_skill.air)
@@ -27,6 +34,7 @@ fn dash(mut query: Query<(&ActionState<Action>, &mut TnuaController, &mut Player
         let basis: Option<(&TnuaBuiltinWalk, &_)> = controller.concrete_basis();
         if let Some(walk) = basis {
             player.used_dashes += 1;
+            skills.send(SkillUsed(Skill::Dash));
             let direction = walk.0.desired_forward;
             controller.action(TnuaBuiltinDash {
                 displacement: direction.normalize_or_zero() * player.max_speed() * 0.75,
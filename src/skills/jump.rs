@@ -0,0 +1,88 @@
+use bevy::{audio::Volume, prelude::*};
+use bevy_ecs::system::Query;
+
+use crate::{input::*, level::Level, AppState, InGameState};
+pub struct JumpPlugin;
+
+impl Plugin for JumpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (air_jump_cue.after(crate::player::move_player), air_jump_cue_fade)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+    }
+}
+
+#[derive(Component)]
+struct AirJumpCue {
+    timer: Timer,
+}
+
+fn air_jump_cue(
+    mut commands: Commands,
+    player: Query<(&Transform, &Player)>,
+    level: Query<Entity, With<Level>>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut last_jump_index: Local<usize>,
+) {
+    let (player_transform, player) = player.single();
+    if player.jump_index <= *last_jump_index {
+        *last_jump_index = player.jump_index;
+        return;
+    }
+    *last_jump_index = player.jump_index;
+    let Ok(level) = level.get_single() else {
+        return;
+    };
+    let color = player.jump_skill.tier.color();
+    let material = materials.add(StandardMaterial {
+        base_color: color,
+        emissive: color,
+        unlit: true,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    commands
+        .spawn((
+            AirJumpCue {
+                timer: Timer::from_seconds(0.4, TimerMode::Once),
+            },
+            PbrBundle {
+                mesh: asset_server.load("hollow_cylinder.obj"),
+                material,
+                transform: Transform::from_translation(
+                    player_transform.translation - Vec3::Y,
+                )
+                .with_scale(Vec3::new(0.5, 0.05, 0.5)),
+                ..default()
+            },
+        ))
+        .set_parent(level);
+    // Each extra jump in the chain plays the cue a little higher.
+    commands.spawn(AudioBundle {
+        source: asset_server.load("upgrade.mp3"),
+        settings: PlaybackSettings {
+            mode: bevy::audio::PlaybackMode::Despawn,
+            volume: Volume::new(0.2),
+            speed: 1. + 0.25 * player.jump_index as f32,
+            ..default()
+        },
+    });
+}
+
+fn air_jump_cue_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cues: Query<(Entity, &mut AirJumpCue, &mut Transform)>,
+) {
+    for (entity, mut cue, mut transform) in cues.iter_mut() {
+        cue.timer.tick(time.delta());
+        let radius = crate::interpolate(0.5, 2., cue.timer.fraction());
+        transform.scale = Vec3::new(radius, 0.05, radius);
+        if cue.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy_ecs::system::Query;
+use bevy_rapier3d::plugin::{RapierConfiguration, TimestepMode};
+use leafwing_input_manager::action_state::ActionState;
+
+use crate::{
+    events::{Skill, SkillUsed},
+    input::*,
+    AppState, InGameState,
+};
+pub struct TimeSlowPlugin;
+
+impl Plugin for TimeSlowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (time_slow, time_slow_cooldown)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(Update, time_slow_audio);
+        app.add_systems(OnEnter(InGameState::End), reset_time_scale);
+        app.add_systems(OnExit(AppState::InGame), reset_time_scale);
+    }
+}
+
+fn time_slow(
+    mut query: Query<(&ActionState<Action>, &mut Player)>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut physics: ResMut<RapierConfiguration>,
+    real_time: Res<Time<Real>>,
+    mut skills: EventWriter<SkillUsed>,
+) {
+    let (action_state, mut player) = query.single_mut();
+    if player.time_slow_timer.is_none()
+        && player.time_slow_skill.max_uses > player.used_time_slows
+        && action_state.just_pressed(&Action::TimeSlow)
+    {
+        player.used_time_slows += 1;
+        skills.send(SkillUsed(Skill::TimeSlow));
+        player.time_slow_timer = Some(Timer::new(
+            player.time_slow_skill.max_duration,
+            TimerMode::Once,
+        ));
+        if player.time_slow_cooldown.is_none() {
+            player.time_slow_cooldown = Some(Timer::new(
+                player.time_slow_skill.cooldown,
+                TimerMode::Once,
+            ));
+        }
+        set_time_scale(
+            player.time_slow_skill.time_scale,
+            &mut virtual_time,
+            &mut physics,
+        );
+    }
+    // The duration is measured in real time so slowing time doesn't also stretch the skill.
+    let finished = match &mut player.time_slow_timer {
+        Some(timer) => timer.tick(real_time.delta()).finished(),
+        None => false,
+    };
+    if finished {
+        player.time_slow_timer = None;
+        set_time_scale(1., &mut virtual_time, &mut physics);
+    }
+}
+
+fn time_slow_cooldown(mut player: Query<&mut Player>, time: Res<Time<Real>>) {
+    let mut player = player.single_mut();
+    if let Some(ref mut cooldown) = player.time_slow_cooldown {
+        cooldown.tick(time.delta());
+        if cooldown.just_finished() {
+            player.used_time_slows -= 1;
+            if player.used_time_slows == 0 {
+                player.time_slow_cooldown = None;
+            } else {
+                player.time_slow_cooldown =
+                    Some(Timer::new(player.time_slow_skill.cooldown, TimerMode::Once));
+            }
+        }
+    }
+}
+
+/// Keeps every playing sound pitched to the current virtual time speed. Sounds started since the
+/// last run still play at their own speed, the others were already scaled by `last_speed`.
+fn time_slow_audio(
+    virtual_time: Res<Time<Virtual>>,
+    sinks: Query<Ref<AudioSink>>,
+    mut last_speed: Local<Option<f32>>,
+) {
+    let speed = virtual_time.relative_speed();
+    let last = last_speed.unwrap_or(1.);
+    for sink in sinks.iter() {
+        if sink.is_added() {
+            if speed != 1. {
+                sink.set_speed(sink.speed() * speed);
+            }
+        } else if speed != last {
+            sink.set_speed(sink.speed() / last * speed);
+        }
+    }
+    *last_speed = Some(speed);
+}
+
+fn reset_time_scale(
+    mut player: Query<&mut Player>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut physics: ResMut<RapierConfiguration>,
+) {
+    if let Ok(mut player) = player.get_single_mut() {
+        player.time_slow_timer = None;
+    }
+    set_time_scale(1., &mut virtual_time, &mut physics);
+}
+
+/// Rapier steps with virtual time, so its maximum step is scaled alongside it to keep the
+/// same number of physics steps per slowed second.
+fn set_time_scale(scale: f32, virtual_time: &mut Time<Virtual>, physics: &mut RapierConfiguration) {
+    virtual_time.set_relative_speed(scale);
+    if let TimestepMode::Variable { ref mut max_dt, .. } = physics.timestep_mode {
+        *max_dt = scale / 60.;
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_tnua::controller::TnuaController;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{PlayerLanded, Skill, SkillUsed},
+    game_mode::GameMode,
+    generate::{self, Generator},
+    hud::{progress::PersonalBest, EndScreen},
+    input::Player,
+    level::Level,
+    AppState, InGameState,
+};
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>();
+        app.init_resource::<StatsTracker>();
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            (start_run_stats, load_personal_best),
+        );
+        app.add_systems(
+            Update,
+            (track_run_stats, count_skills, count_near_misses)
+                .run_if(in_state(AppState::InGame).and_then(in_state(InGameState::Playing))),
+        );
+        app.add_systems(OnEnter(InGameState::End), record_run_stats);
+        app.add_systems(OnExit(AppState::InGame), record_abandoned_run);
+    }
+}
+
+/// Landing this close to the far edge of a hole, in world units, counts as a near miss.
+const NEAR_MISS_MARGIN: f32 = 0.5;
+
+/// One line of `stats.jsonl`, written when a run ends.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RunStats {
+    pub seed: String,
+    #[serde(default = "crate::scores::first_generator_version")]
+    pub generator_version: u8,
+    pub mode: GameMode,
+    /// Seconds since the unix epoch when the run ended.
+    pub finished: u64,
+    pub duration: f32,
+    pub distance: f32,
+    pub score: f32,
+    pub jumps: u32,
+    pub air_jumps: u32,
+    pub dashes: u32,
+    pub glides: u32,
+    pub top_speed: f32,
+    pub airborne: f32,
+    pub near_misses: u32,
+    /// Distance covered in each minute of the run, the last one possibly partial.
+    pub distance_per_minute: Vec<f32>,
+}
+
+pub fn stats_path() -> PathBuf {
+    crate::benchmark::profile_dir().join("stats.jsonl")
+}
+
+/// Every recorded run, oldest first. Lines that don't parse are skipped.
+pub fn load_run_stats() -> Vec<RunStats> {
+    fs::read_to_string(stats_path())
+        .map(|stats| {
+            stats
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn append_run_stats(stats: &RunStats) -> std::io::Result<()> {
+    let path = stats_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(stats)?)
+}
+
+/// Picks the furthest earlier run on this seed, generator version and mode as the one to race.
+fn load_personal_best(mut commands: Commands, generator: Res<Generator>, mode: Res<GameMode>) {
+    let seed = crate::scores::seed_label(&generator.get_seed());
+    let best = load_run_stats()
+        .into_iter()
+        .filter(|run| {
+            run.seed == seed && run.generator_version == generator.version() && run.mode == *mode
+        })
+        .max_by(|a, b| a.distance.total_cmp(&b.distance));
+    commands.insert_resource(
+        best.map_or_else(PersonalBest::default, |run| PersonalBest::from_run(&run)),
+    );
+}
+
+/// Distance at the start of the current minute, and whether the run has been written yet.
+#[derive(Resource, Default)]
+struct StatsTracker {
+    minute_start: f32,
+    recorded: bool,
+}
+
+fn start_run_stats(mut commands: Commands, generator: Res<Generator>, mode: Res<GameMode>) {
+    commands.insert_resource(StatsTracker::default());
+    commands.insert_resource(RunStats {
+        seed: crate::scores::seed_label(&generator.get_seed()),
+        generator_version: generator.version(),
+        mode: *mode,
+        ..default()
+    });
+}
+
+fn count_skills(mut stats: ResMut<RunStats>, mut skills: EventReader<SkillUsed>) {
+    for SkillUsed(skill) in skills.read() {
+        match skill {
+            Skill::Jump => stats.jumps += 1,
+            Skill::AirJump { .. } => stats.air_jumps += 1,
+            Skill::Dash => stats.dashes += 1,
+            Skill::Glide => stats.glides += 1,
+            Skill::TimeSlow => {}
+        }
+    }
+}
+
+fn count_near_misses(
+    mut stats: ResMut<RunStats>,
+    mut landed: EventReader<PlayerLanded>,
+    mut generator: ResMut<Generator>,
+) {
+    for PlayerLanded { position } in landed.read() {
+        let column = generate::column(position.x);
+        let near_edge = column as f32 * generate::COLUMN_WIDTH - generate::COLUMN_WIDTH / 2.;
+        if column > 0 && generator.is_gap(column - 1) && position.x - near_edge < NEAR_MISS_MARGIN {
+            stats.near_misses += 1;
+        }
+    }
+}
+
+fn track_run_stats(
+    mut stats: ResMut<RunStats>,
+    mut tracker: ResMut<StatsTracker>,
+    player: Query<(&Velocity, &TnuaController, &Player)>,
+    level: Query<&Level>,
+    time: Res<Time>,
+) {
+    let Ok((velocity, controller, player)) = player.get_single() else {
+        return;
+    };
+    let Ok(level) = level.get_single() else {
+        return;
+    };
+    if controller.is_airborne().unwrap_or(false) {
+        stats.airborne += time.delta_seconds();
+    }
+    stats.top_speed = stats.top_speed.max(velocity.linvel.x);
+    let elapsed = level.elapsed.as_secs_f32();
+    if elapsed >= 60. * (stats.distance_per_minute.len() + 1) as f32 {
+        stats
+            .distance_per_minute
+            .push(player.distance - tracker.minute_start);
+        tracker.minute_start = player.distance;
+    }
+    stats.duration = elapsed;
+    stats.distance = player.distance;
+}
+
+fn save_run_stats(stats: &mut RunStats, tracker: &mut StatsTracker, player: Option<&Player>) {
+    if let Some(player) = player {
+        stats.score = player.score;
+        let recorded: f32 = stats.distance_per_minute.iter().sum();
+        if player.distance > recorded {
+            stats.distance_per_minute.push(player.distance - recorded);
+        }
+    }
+    stats.finished = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    if let Err(err) = append_run_stats(stats) {
+        error!("Failed to save run statistics: {}", err);
+    }
+    tracker.recorded = true;
+}
+
+/// Runs left from the pause menu never reach `InGameState::End`, they are saved as they stand.
+fn record_abandoned_run(
+    mut stats: ResMut<RunStats>,
+    mut tracker: ResMut<StatsTracker>,
+    player: Query<&Player>,
+) {
+    if !tracker.recorded {
+        save_run_stats(&mut stats, &mut tracker, player.get_single().ok());
+    }
+}
+
+fn record_run_stats(
+    mut commands: Commands,
+    mut stats: ResMut<RunStats>,
+    mut tracker: ResMut<StatsTracker>,
+    player: Query<&Player>,
+    end_screen: Query<Entity, With<EndScreen>>,
+) {
+    save_run_stats(&mut stats, &mut tracker, player.get_single().ok());
+    let summary = format!(
+        "Distance {:.0} in {:.0}s - Top speed {:.1}\nJumps {} - Air jumps {} - Dashes {} - Glides {}\nAirborne {:.0}s - Near misses {}",
+        stats.distance,
+        stats.duration,
+        stats.top_speed,
+        stats.jumps,
+        stats.air_jumps,
+        stats.dashes,
+        stats.glides,
+        stats.airborne,
+        stats.near_misses
+    );
+    for screen in end_screen.iter() {
+        commands.entity(screen).with_children(|screen| {
+            screen.spawn(
+                TextBundle::from_section(
+                    summary.clone(),
+                    TextStyle {
+                        font_size: 24.,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+        });
+    }
+}
@@ -60,6 +60,7 @@ pub enum UpgradeType {
     JumpSkill(JumpSkill),
     DashSkill(DashSkill),
     GlideSkill(GlideSkill),
+    TimeSlowSkill(TimeSlowSkill),
 }
 
 
@@ -101,6 +102,14 @@ pub struct JumpSkill {
     pub tier: UpgradeLevel,
     pub air: bool,
 }
+impl JumpSkill {
+    /// Each extra jump reaches this fraction of the height of the one before it.
+    pub const AIR_JUMP_DECAY: f32 = 0.75;
+    /// Height of a jump, where `jump_index` 0 is the grounded jump and 1.. are the air jumps.
+    pub fn jump_height(&self, jump_power: f32, jump_index: usize) -> f32 {
+        jump_power * Self::AIR_JUMP_DECAY.powi(jump_index as i32)
+    }
+}
 #[derive(Debug, Copy, Clone, Default)]
 pub struct DashSkill {
     pub max_dash: u8,
@@ -117,6 +126,30 @@ pub struct GlideSkill {
     pub max_duration: Duration,
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct TimeSlowSkill {
+    pub max_uses: u8,
+    pub cooldown: Duration,
+    pub tier: UpgradeLevel,
+    pub max_duration: Duration,
+    /// Relative speed of virtual time while the skill is active.
+    pub time_scale: f32,
+    /// Whether the run timer is slowed along with everything else.
+    pub slows_timer: bool,
+}
+impl Default for TimeSlowSkill {
+    fn default() -> Self {
+        Self {
+            max_uses: 0,
+            cooldown: Duration::ZERO,
+            tier: UpgradeLevel::None,
+            max_duration: Duration::ZERO,
+            time_scale: 1.,
+            slows_timer: true,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct StatUpgrade {
     pub modifier: f32,
@@ -147,6 +180,10 @@ impl Upgrade<UpgradeType> for UpgradeType {
                 UpgradeType::GlideSkill(other) => me.tier <= other.tier,
                 _ => false,
             },
+            UpgradeType::TimeSlowSkill(me) => match other {
+                UpgradeType::TimeSlowSkill(other) => me.tier <= other.tier,
+                _ => false,
+            },
         }
     }
     
@@ -161,6 +198,7 @@ impl Upgrade<UpgradeType> for UpgradeType {
             UpgradeType::JumpSkill(jump) => jump.tier,
             UpgradeType::DashSkill(dash) => dash.tier,
             UpgradeType::GlideSkill(glide) => glide.tier,
+            UpgradeType::TimeSlowSkill(time_slow) => time_slow.tier,
         }
     }
     
@@ -231,6 +269,17 @@ mod tests {
             })
         )
     }
+    #[test]
+    fn air_jumps_decay() {
+        let skill = JumpSkill {
+            max_jumps: 3,
+            tier: UpgradeLevel::Advanced,
+            air: true,
+        };
+        assert_eq!(skill.jump_height(4., 0), 4.);
+        assert_eq!(skill.jump_height(4., 1), 3.);
+        assert!(skill.jump_height(4., 2) < skill.jump_height(4., 1));
+    }
     #[derive(EnumIter, EnumCount, Debug, PartialEq, Copy, Clone)]
     enum UpgradeType {
         Speed,